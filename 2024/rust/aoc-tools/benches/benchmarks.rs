@@ -1,6 +1,29 @@
-use aoc_tools::*;
+use aoc_tools::grid::{self, Grid};
 
 fn main() {
   // Run registered benchmarks.
   divan::main();
 }
+
+fn letters() -> String {
+  (0..140)
+    .map(|y| {
+      (0..140)
+        .map(|x| b"XMAS"[(x * 7 + y * 3) % 4] as char)
+        .collect::<String>()
+        + "\n"
+    })
+    .collect()
+}
+
+#[divan::bench]
+fn parse(bencher: divan::Bencher) {
+  let input = letters();
+  bencher.bench(|| grid::parse(divan::black_box(&input), Ok).unwrap());
+}
+
+#[divan::bench]
+fn rotate(bencher: divan::Bencher) {
+  let grid: Grid<u8> = grid::parse(&letters(), Ok).unwrap();
+  bencher.bench(|| divan::black_box(&grid).rotate_cw());
+}
@@ -0,0 +1,247 @@
+use std::fmt::Display;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A puzzle answer.
+///
+/// Answers compare by value across variants: numbers compare numerically and
+/// anything else by its text, so `Answer::UInt(143) == "143"`.
+#[derive(Clone, Debug)]
+pub enum Answer {
+  Int(i64),
+  UInt(u64),
+  Str(String),
+  /// Multi-line ASCII art, e.g. letters drawn by a simulation.
+  Art(String),
+}
+
+impl Answer {
+  pub fn art(art: impl Into<String>) -> Self {
+    Answer::Art(art.into())
+  }
+
+  pub fn is_multiline(&self) -> bool {
+    match self {
+      Answer::Art(_) => true,
+      Answer::Str(s) => s.contains('\n'),
+      _ => false,
+    }
+  }
+
+  fn as_i128(&self) -> Option<i128> {
+    match self {
+      Answer::Int(n) => Some(*n as i128),
+      Answer::UInt(n) => Some(*n as i128),
+      _ => None,
+    }
+  }
+
+  fn eq_int(&self, n: i128) -> bool {
+    match self {
+      Answer::Int(_) | Answer::UInt(_) => self.as_i128() == Some(n),
+      Answer::Str(s) | Answer::Art(s) => format!("{}", n) == *s,
+    }
+  }
+
+  fn eq_str(&self, s: &str) -> bool {
+    match self {
+      Answer::Int(_) | Answer::UInt(_) => format!("{}", self) == s,
+      Answer::Str(t) | Answer::Art(t) => t == s,
+    }
+  }
+}
+
+impl Display for Answer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Answer::Int(n) => write!(f, "{}", n),
+      Answer::UInt(n) => write!(f, "{}", n),
+      Answer::Str(s) | Answer::Art(s) => write!(f, "{}", s),
+    }
+  }
+}
+
+impl PartialEq for Answer {
+  fn eq(&self, other: &Self) -> bool {
+    match other {
+      Answer::Int(n) => self.eq_int(*n as i128),
+      Answer::UInt(n) => self.eq_int(*n as i128),
+      Answer::Str(s) | Answer::Art(s) => self.eq_str(s),
+    }
+  }
+}
+
+macro_rules! answer_from {
+  ($variant:ident: $($t:ty),*) => {
+    $(
+      impl From<$t> for Answer {
+        fn from(value: $t) -> Self {
+          Answer::$variant(value as _)
+        }
+      }
+
+      impl PartialEq<$t> for Answer {
+        fn eq(&self, other: &$t) -> bool {
+          self.eq_int(*other as i128)
+        }
+      }
+
+      impl PartialEq<Answer> for $t {
+        fn eq(&self, other: &Answer) -> bool {
+          other.eq_int(*self as i128)
+        }
+      }
+    )*
+  };
+}
+
+answer_from!(Int: i8, i16, i32, i64, isize);
+answer_from!(UInt: u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+  fn from(value: String) -> Self {
+    Answer::Str(value)
+  }
+}
+
+impl From<&str> for Answer {
+  fn from(value: &str) -> Self {
+    Answer::Str(value.to_owned())
+  }
+}
+
+impl PartialEq<str> for Answer {
+  fn eq(&self, other: &str) -> bool {
+    self.eq_str(other)
+  }
+}
+
+impl PartialEq<&str> for Answer {
+  fn eq(&self, other: &&str) -> bool {
+    self.eq_str(other)
+  }
+}
+
+impl PartialEq<Answer> for &str {
+  fn eq(&self, other: &Answer) -> bool {
+    other.eq_str(self)
+  }
+}
+
+impl PartialEq<String> for Answer {
+  fn eq(&self, other: &String) -> bool {
+    self.eq_str(other)
+  }
+}
+
+impl PartialEq<Answer> for String {
+  fn eq(&self, other: &Answer) -> bool {
+    other.eq_str(self)
+  }
+}
+
+/// Numbers serialize as integers where the format allows (TOML integers are
+/// signed 64-bit), and everything else as a string.
+impl Serialize for Answer {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      Answer::Int(n) => serializer.serialize_i64(*n),
+      Answer::UInt(n) => match i64::try_from(*n) {
+        Ok(n) => serializer.serialize_i64(n),
+        Err(_) => serializer.serialize_str(&n.to_string()),
+      },
+      Answer::Str(s) | Answer::Art(s) => serializer.serialize_str(s),
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct AnswerVisitor;
+
+    impl Visitor<'_> for AnswerVisitor {
+      type Value = Answer;
+
+      fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "an integer or string answer")
+      }
+
+      fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(Answer::Int(n))
+      }
+
+      fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(Answer::UInt(n))
+      }
+
+      fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+        Ok(if s.contains('\n') {
+          Answer::art(s)
+        } else {
+          Answer::from(s)
+        })
+      }
+    }
+
+    deserializer.deserialize_any(AnswerVisitor)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(Answer::Int(143), Answer::UInt(143))]
+  #[case(Answer::UInt(143), Answer::from("143"))]
+  #[case(Answer::art("#.\n.#\n"), Answer::from("#.\n.#\n"))]
+  fn test_eq(#[case] a: Answer, #[case] b: Answer) {
+    assert_eq!(a, b);
+    assert_eq!(b, a);
+  }
+
+  #[rstest]
+  #[case(Answer::Int(-1), Answer::UInt(u64::MAX))]
+  #[case(Answer::UInt(143), Answer::from("0143"))]
+  fn test_ne(#[case] a: Answer, #[case] b: Answer) {
+    assert_ne!(a, b);
+  }
+
+  #[test]
+  fn test_eq_literals() {
+    assert_eq!(Answer::from(18usize), 18);
+    assert_eq!(18u32, Answer::from(18usize));
+    assert_eq!("18", Answer::from(18usize));
+    assert_eq!(Answer::from("abc"), "abc".to_string());
+  }
+
+  #[test]
+  fn test_serde_round_trip() -> anyhow::Result<()> {
+    #[derive(Serialize, Deserialize)]
+    struct Doc {
+      a: Answer,
+      b: Answer,
+      c: Answer,
+      d: Answer,
+    }
+    let doc = Doc {
+      a: Answer::Int(-3),
+      b: Answer::UInt(u64::MAX),
+      c: Answer::from("abc"),
+      d: Answer::art("#.\n.#"),
+    };
+    let text = toml::to_string(&doc)?;
+    assert_eq!(
+      "a = -3\nb = \"18446744073709551615\"\nc = \"abc\"\nd = \"\"\"\n#.\n.#\"\"\"\n",
+      text
+    );
+    let back: Doc = toml::from_str(&text)?;
+    assert_eq!(doc.a, back.a);
+    assert_eq!(doc.b, back.b);
+    assert!(matches!(back.d, Answer::Art(_)));
+    Ok(())
+  }
+}
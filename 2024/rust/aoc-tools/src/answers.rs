@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{Answer, Part};
+
+/// Accepted answers for one day.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DayAnswers {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub part1: Option<Answer>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub part2: Option<Answer>,
+}
+
+impl DayAnswers {
+  pub fn get(&self, part: Part) -> Option<&Answer> {
+    match part {
+      Part::One => self.part1.as_ref(),
+      Part::Two => self.part2.as_ref(),
+    }
+  }
+
+  /// The parts that have an accepted answer.
+  pub fn parts(&self) -> Vec<Part> {
+    Part::BOTH
+      .into_iter()
+      .filter(|part| self.get(*part).is_some())
+      .collect()
+  }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+  Match,
+  Mismatch {
+    expected: Answer,
+  },
+  /// No answer has been recorded for this part yet.
+  Unknown,
+}
+
+/// The registry of accepted answers, stored as TOML keyed by day and part:
+///
+/// ```toml
+/// [day-01]
+/// part1 = 11
+/// part2 = 31
+/// ```
+///
+/// `answers.example.toml` in the workspace is a commented sample.
+#[derive(Clone, Debug)]
+pub struct Answers {
+  path: PathBuf,
+  days: BTreeMap<String, DayAnswers>,
+}
+
+impl Answers {
+  /// Load the registry at `path`; a missing file is an empty registry.
+  pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+    let path = path.into();
+    let days = match std::fs::read_to_string(&path) {
+      Ok(text) => toml::from_str(&text).with_context(|| format!("parse {}", path.display()))?,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+      Err(e) => return Err(e).with_context(|| format!("read {}", path.display())),
+    };
+    Ok(Answers { path, days })
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn day(&self, day: &str) -> Option<&DayAnswers> {
+    self.days.get(day)
+  }
+
+  pub fn days(&self) -> impl Iterator<Item = (&str, &DayAnswers)> {
+    self
+      .days
+      .iter()
+      .map(|(day, answers)| (day.as_str(), answers))
+  }
+
+  pub fn get(&self, day: &str, part: Part) -> Option<&Answer> {
+    self.day(day).and_then(|answers| answers.get(part))
+  }
+
+  pub fn set(&mut self, day: &str, part: Part, answer: impl Into<Answer>) {
+    let answers = self.days.entry(day.to_owned()).or_default();
+    match part {
+      Part::One => answers.part1 = Some(answer.into()),
+      Part::Two => answers.part2 = Some(answer.into()),
+    }
+  }
+
+  pub fn check(&self, day: &str, part: Part, actual: &Answer) -> Verdict {
+    match self.get(day, part) {
+      Some(expected) if expected == actual => Verdict::Match,
+      Some(expected) => Verdict::Mismatch {
+        expected: expected.clone(),
+      },
+      None => Verdict::Unknown,
+    }
+  }
+
+  pub fn save(&self) -> anyhow::Result<()> {
+    let text = toml::to_string(&self.days)?;
+    std::fs::write(&self.path, text).with_context(|| format!("write {}", self.path.display()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_missing_file_is_empty() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let answers = Answers::load(dir.path().join("answers.toml"))?;
+    assert_eq!(0, answers.days().count());
+    assert_eq!(
+      Verdict::Unknown,
+      answers.check("day-01", Part::One, &11.into())
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_example_file() -> anyhow::Result<()> {
+    let answers = Answers::load(concat!(
+      env!("CARGO_MANIFEST_DIR"),
+      "/../answers.example.toml"
+    ))?;
+    assert_eq!(
+      Verdict::Match,
+      answers.check("day-01", Part::Two, &31.into())
+    );
+    assert_eq!(vec![Part::One], answers.day("day-03").unwrap().parts());
+    Ok(())
+  }
+
+  #[test]
+  fn test_round_trip() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("answers.toml");
+    let mut answers = Answers::load(&path)?;
+    answers.set("day-01", Part::One, 11);
+    answers.set("day-02", Part::Two, "abc");
+    answers.save()?;
+
+    assert_eq!(
+      "[day-01]\npart1 = 11\n\n[day-02]\npart2 = \"abc\"\n",
+      std::fs::read_to_string(&path)?
+    );
+    let answers = Answers::load(&path)?;
+    assert_eq!(
+      Verdict::Match,
+      answers.check("day-01", Part::One, &11usize.into())
+    );
+    assert_eq!(
+      Verdict::Mismatch {
+        expected: 11.into()
+      },
+      answers.check("day-01", Part::One, &12.into())
+    );
+    assert_eq!(
+      Verdict::Match,
+      answers.check("day-02", Part::Two, &"abc".into())
+    );
+
+    assert_eq!(vec![Part::Two], answers.day("day-02").unwrap().parts());
+
+    // Numbers recorded as strings still match numeric answers.
+    std::fs::write(&path, "[day-01]\npart1 = \"11\"\n")?;
+    let answers = Answers::load(&path)?;
+    assert_eq!(
+      Verdict::Match,
+      answers.check("day-01", Part::One, &11.into())
+    );
+    Ok(())
+  }
+}
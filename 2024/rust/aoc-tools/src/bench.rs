@@ -0,0 +1,387 @@
+//! Benchmark history: each divan run recorded as JSON lines, one row per
+//! benchmark, so runs can be compared with each other.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+/// One benchmark's timings from one run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BenchRow {
+  pub day: String,
+  /// The divan benchmark path, e.g. `parse` or `solve::part1`.
+  pub bench: String,
+  pub median_ns: f64,
+  pub mean_ns: f64,
+  pub samples: u64,
+  /// The git revision benchmarked, suffixed with `+dirty` if the tree had
+  /// uncommitted changes.
+  pub rev: String,
+  /// When the run was recorded, as UTC RFC 3339.
+  pub date: String,
+}
+
+impl BenchRow {
+  pub fn median(&self) -> Duration {
+    Duration::from_nanos(self.median_ns as u64)
+  }
+}
+
+/// A benchmark's timings as parsed from divan's output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchResult {
+  pub bench: String,
+  pub median_ns: f64,
+  pub mean_ns: f64,
+  pub samples: u64,
+}
+
+impl BenchResult {
+  /// This result as a row of a run of `day` at `rev` on `date`.
+  pub fn row(&self, day: &str, rev: &str, date: &str) -> BenchRow {
+    BenchRow {
+      day: day.to_owned(),
+      bench: self.bench.clone(),
+      median_ns: self.median_ns,
+      mean_ns: self.mean_ns,
+      samples: self.samples,
+      rev: rev.to_owned(),
+      date: date.to_owned(),
+    }
+  }
+}
+
+/// Parse the table divan prints, naming nested benchmarks by their module
+/// path. Allocation counts and other extra lines are skipped.
+pub fn parse_divan(output: &str) -> anyhow::Result<Vec<BenchResult>> {
+  let mut lines = output.lines().skip_while(|line| !line.contains("fastest"));
+  let Some(header) = lines.next() else {
+    bail!("no divan results found in benchmark output");
+  };
+  // Columns line up by character, so the header's separators split each row.
+  let columns = header
+    .chars()
+    .enumerate()
+    .filter(|(_, c)| *c == '│')
+    .map(|(i, _)| i)
+    .collect::<Vec<_>>();
+
+  let mut path: Vec<String> = Vec::new();
+  let mut results = Vec::new();
+  for line in lines {
+    let chars = line.chars().collect::<Vec<_>>();
+    let cells = std::iter::once(0)
+      .chain(columns.iter().map(|i| i + 1))
+      .zip(columns.iter().copied().chain([usize::MAX]))
+      .map(|(start, end)| {
+        let end = end.min(chars.len());
+        chars
+          .get(start..end)
+          .unwrap_or_default()
+          .iter()
+          .collect::<String>()
+      })
+      .collect::<Vec<_>>();
+
+    let Some(branch) = cells[0].find(['├', '╰']) else {
+      continue;
+    };
+    let depth = cells[0][..branch].chars().count() / 3;
+    let mut rest = cells[0][branch..]
+      .trim_start_matches(['├', '╰', '─'])
+      .split_whitespace();
+    let Some(name) = rest.next() else {
+      continue;
+    };
+    path.truncate(depth);
+    path.push(name.to_owned());
+
+    let fastest = rest.collect::<Vec<_>>().join(" ");
+    if fastest.is_empty() {
+      // A group of benchmarks rather than a benchmark.
+      continue;
+    }
+    let bench = path.join("::");
+    let cell = |i: usize| cells.get(i).map_or("", |cell| cell.trim());
+    results.push(BenchResult {
+      median_ns: parse_duration(cell(2)).with_context(|| format!("median of {}", bench))?,
+      mean_ns: parse_duration(cell(3)).with_context(|| format!("mean of {}", bench))?,
+      samples: cell(4)
+        .parse()
+        .with_context(|| format!("samples of {}", bench))?,
+      bench,
+    });
+  }
+  Ok(results)
+}
+
+/// Parse a duration as divan prints it, e.g. `46.12 µs`, into nanoseconds.
+fn parse_duration(text: &str) -> anyhow::Result<f64> {
+  let (value, unit) = text
+    .split_once(' ')
+    .with_context(|| format!("bad duration {:?}", text))?;
+  let scale = match unit {
+    "ps" => 1e-3,
+    "ns" => 1.0,
+    "µs" | "us" => 1e3,
+    "ms" => 1e6,
+    "s" => 1e9,
+    _ => bail!("unknown duration unit {:?}", unit),
+  };
+  let value = value
+    .parse::<f64>()
+    .with_context(|| format!("bad duration {:?}", text))?;
+  Ok(value * scale)
+}
+
+/// The history of benchmark runs, stored as one JSON [`BenchRow`] per line.
+#[derive(Clone, Debug)]
+pub struct History {
+  path: PathBuf,
+  rows: Vec<BenchRow>,
+}
+
+impl History {
+  /// Load the history at `path`; a missing file is an empty history.
+  pub fn load(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+    let path = path.into();
+    let text = match std::fs::read_to_string(&path) {
+      Ok(text) => text,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+      Err(e) => return Err(e).with_context(|| format!("read {}", path.display())),
+    };
+    let rows = text
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.trim().is_empty())
+      .map(|(i, line)| {
+        serde_json::from_str(line).with_context(|| format!("{}:{}", path.display(), i + 1))
+      })
+      .collect::<anyhow::Result<_>>()?;
+    Ok(History { path, rows })
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn rows(&self) -> &[BenchRow] {
+    &self.rows
+  }
+
+  /// Record a run, appending its rows to the file.
+  pub fn append(&mut self, rows: Vec<BenchRow>) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new()
+      .create(true)
+      .append(true)
+      .open(&self.path)
+      .with_context(|| format!("open {}", self.path.display()))?;
+    for row in &rows {
+      writeln!(file, "{}", serde_json::to_string(row)?)
+        .with_context(|| format!("write {}", self.path.display()))?;
+    }
+    self.rows.extend(rows);
+    Ok(())
+  }
+
+  /// The recorded runs of `day`, oldest first. A run is the rows recorded
+  /// together, sharing a date and revision.
+  pub fn runs(&self, day: &str) -> Vec<Vec<&BenchRow>> {
+    let mut runs: Vec<Vec<&BenchRow>> = Vec::new();
+    for row in self.rows.iter().filter(|row| row.day == day) {
+      match runs.last_mut() {
+        Some(run) if run[0].date == row.date && run[0].rev == row.rev => run.push(row),
+        _ => runs.push(vec![row]),
+      }
+    }
+    runs
+  }
+}
+
+/// How one benchmark's median moved between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change<'a> {
+  pub bench: &'a str,
+  /// The baseline row, if the baseline run had this benchmark.
+  pub before: Option<&'a BenchRow>,
+  pub after: &'a BenchRow,
+}
+
+impl Change<'_> {
+  /// The relative change in median time, e.g. `0.1` for 10% slower.
+  pub fn change(&self) -> Option<f64> {
+    self
+      .before
+      .map(|before| self.after.median_ns / before.median_ns - 1.0)
+  }
+
+  /// Whether the median grew by more than `threshold`, e.g. `0.05` for 5%.
+  pub fn is_regression(&self, threshold: f64) -> bool {
+    self.change().is_some_and(|change| change > threshold)
+  }
+}
+
+/// Match up the benchmarks of run `after` with those of `before`.
+pub fn compare<'a>(before: &[&'a BenchRow], after: &[&'a BenchRow]) -> Vec<Change<'a>> {
+  after
+    .iter()
+    .map(|row| Change {
+      bench: &row.bench,
+      before: before.iter().copied().find(|b| b.bench == row.bench),
+      after: row,
+    })
+    .collect()
+}
+
+/// The current git revision of the repository at `dir`, or `"unknown"` if it
+/// can't be found.
+pub fn git_rev(dir: &Path) -> String {
+  let git = |args: &[&str]| {
+    Command::new("git")
+      .args(args)
+      .current_dir(dir)
+      .output()
+      .ok()
+  };
+  let Some(rev) = git(&["rev-parse", "--short", "HEAD"]).filter(|out| out.status.success()) else {
+    return "unknown".to_owned();
+  };
+  let rev = String::from_utf8_lossy(&rev.stdout).trim().to_owned();
+  let dirty = git(&["status", "--porcelain"]).is_some_and(|out| !out.stdout.is_empty());
+  if dirty {
+    format!("{}+dirty", rev)
+  } else {
+    rev
+  }
+}
+
+/// The current time as UTC RFC 3339, e.g. `2024-12-12T10:40:00Z`.
+pub fn now() -> String {
+  let secs = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_or(0, |d| d.as_secs());
+  format_timestamp(secs)
+}
+
+fn format_timestamp(secs: u64) -> String {
+  // Howard Hinnant's days-to-civil-date algorithm.
+  let days = (secs / 86400) as i64 + 719468;
+  let era = days.div_euclid(146097);
+  let doe = days - era * 146097;
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = doy - (153 * mp + 2) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + (month <= 2) as i64;
+  let time = secs % 86400;
+  format!(
+    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+    year,
+    month,
+    day,
+    time / 3600,
+    time / 60 % 60,
+    time % 60
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  const OUTPUT: &str = "Timer precision: 31 ns
+day_05_bench    fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ parse        136.5 µs      │ 236.6 µs      │ 175.2 µs      │ 172.6 µs      │ 100     │ 100
+│               max alloc:    │               │               │               │         │
+│                 202         │ 202           │ 202           │ 202           │         │
+├─ end_to_end                 │               │               │               │         │
+│  ├─ part1     239.5 µs      │ 296.6 µs      │ 259.2 µs      │ 260 µs        │ 100     │ 100
+│  ╰─ part2     1.357 ms      │ 5.093 ms      │ 1.557 ms      │ 1.659 ms      │ 50      │ 50
+╰─ solve                      │               │               │               │         │
+   ├─ part1     5.537 µs      │ 9.509 µs      │ 5.796 µs      │ 5.854 µs      │ 100     │ 100
+   ╰─ part2     34.58 µs      │ 57.14 µs      │ 36.14 µs      │ 37.2 µs       │ 100     │ 100
+";
+
+  #[test]
+  fn test_parse_divan() -> anyhow::Result<()> {
+    let results = parse_divan(OUTPUT)?;
+    assert_eq!(
+      vec![
+        ("parse", 175_200.0, 100),
+        ("end_to_end::part1", 259_200.0, 100),
+        ("end_to_end::part2", 1_557_000.0, 50),
+        ("solve::part1", 5_796.0, 100),
+        ("solve::part2", 36_140.0, 100),
+      ],
+      results
+        .iter()
+        .map(|r| (r.bench.as_str(), r.median_ns.round(), r.samples))
+        .collect::<Vec<_>>()
+    );
+    assert_eq!(260_000.0, results[1].mean_ns.round());
+    assert!(parse_divan("error: no benches").is_err());
+    Ok(())
+  }
+
+  #[rstest]
+  #[case("239 ns", 239.0)]
+  #[case("46.12 µs", 46_120.0)]
+  #[case("5.909 ms", 5_909_000.0)]
+  #[case("1.5 s", 1_500_000_000.0)]
+  fn test_parse_duration(#[case] text: &str, #[case] ns: f64) -> anyhow::Result<()> {
+    assert_eq!(ns, parse_duration(text)?.round());
+    Ok(())
+  }
+
+  #[rstest]
+  #[case(0, "1970-01-01T00:00:00Z")]
+  #[case(951_782_400, "2000-02-29T00:00:00Z")]
+  #[case(1_734_000_000, "2024-12-12T10:40:00Z")]
+  fn test_format_timestamp(#[case] secs: u64, #[case] expected: &str) {
+    assert_eq!(expected, format_timestamp(secs));
+  }
+
+  #[test]
+  fn test_history() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bench-history.jsonl");
+    let results = parse_divan(OUTPUT)?;
+
+    let mut history = History::load(&path)?;
+    let first = results
+      .iter()
+      .map(|r| r.row("day-05", "abc123", "2024-12-12T10:40:00Z"));
+    history.append(first.collect())?;
+    let second = results.iter().map(|r| {
+      let mut row = r.row("day-05", "def456", "2024-12-13T10:40:00Z");
+      if row.bench == "solve::part2" {
+        row.median_ns *= 1.5;
+      }
+      row
+    });
+    history.append(second.collect())?;
+
+    let history = History::load(&path)?;
+    let runs = history.runs("day-05");
+    assert_eq!(2, runs.len());
+    assert!(history.runs("day-06").is_empty());
+
+    let changes = compare(&runs[0], &runs[1]);
+    let regressed = changes
+      .iter()
+      .filter(|c| c.is_regression(0.1))
+      .map(|c| c.bench)
+      .collect::<Vec<_>>();
+    assert_eq!(vec!["solve::part2"], regressed);
+    assert_eq!(Some(0.0), changes[0].change());
+    Ok(())
+  }
+}
@@ -0,0 +1,155 @@
+//! Cycle detection for sequences `x0, f(x0), f(f(x0)), ...` over a finite
+//! state space, which must eventually repeat.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where a sequence starts repeating: after `mu` steps it enters a cycle of
+/// length `lambda`, so `x[i] == x[i + lambda]` for every `i >= mu`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+  pub mu: usize,
+  pub lambda: usize,
+}
+
+impl Cycle {
+  /// The earliest step whose state equals the state at step `n`.
+  pub fn reduce(&self, n: usize) -> usize {
+    if n < self.mu {
+      n
+    } else {
+      self.mu + (n - self.mu) % self.lambda
+    }
+  }
+}
+
+/// Brent's algorithm: finds the cycle using only equality and constant memory,
+/// at the cost of re-running `f`.
+pub fn brent<S: Clone + PartialEq>(start: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+  // Find lambda by racing a hare ahead of a tortoise that teleports to the
+  // hare at each power of two.
+  let (mut power, mut lambda) = (1, 1);
+  let mut tortoise = start.clone();
+  let mut hare = f(&start);
+  while tortoise != hare {
+    if power == lambda {
+      tortoise = hare.clone();
+      power *= 2;
+      lambda = 0;
+    }
+    hare = f(&hare);
+    lambda += 1;
+  }
+
+  // With the hare lambda steps ahead, they meet at the start of the cycle.
+  let mut tortoise = start.clone();
+  let mut hare = start;
+  for _ in 0..lambda {
+    hare = f(&hare);
+  }
+  let mut mu = 0;
+  while tortoise != hare {
+    tortoise = f(&tortoise);
+    hare = f(&hare);
+    mu += 1;
+  }
+  Cycle { mu, lambda }
+}
+
+/// Finds the cycle by remembering when each state was first seen. Calls `f`
+/// only `mu + lambda` times.
+pub fn hashed<S: Clone + Eq + Hash>(start: S, f: impl FnMut(&S) -> S) -> Cycle {
+  History::new(start, f, usize::MAX).cycle.unwrap()
+}
+
+/// The state after `n` applications of `f`, skipping whole trips around the
+/// cycle once one is found.
+pub fn nth_state<S: Clone + Eq + Hash>(start: S, f: impl FnMut(&S) -> S, n: usize) -> S {
+  let history = History::new(start, f, n);
+  let index = history.cycle.map_or(n, |cycle| cycle.reduce(n));
+  history.states[index].clone()
+}
+
+/// The states of a sequence up to its first repeat, or up to step `limit`.
+struct History<S> {
+  states: Vec<S>,
+  cycle: Option<Cycle>,
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+  fn new(start: S, mut f: impl FnMut(&S) -> S, limit: usize) -> Self {
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut states = vec![start];
+    while states.len() <= limit {
+      let next = f(states.last().unwrap());
+      if let Some(&mu) = seen.get(&next) {
+        let lambda = states.len() - mu;
+        return History {
+          states,
+          cycle: Some(Cycle { mu, lambda }),
+        };
+      }
+      seen.insert(next.clone(), states.len());
+      states.push(next);
+    }
+    History {
+      states,
+      cycle: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use proptest::prelude::*;
+
+  use super::*;
+
+  /// A sequence that counts up from zero and loops back to `mu` after
+  /// reaching `mu + lambda - 1`.
+  fn rho(mu: usize, lambda: usize) -> impl Fn(&usize) -> usize {
+    move |&x| if x + 1 < mu + lambda { x + 1 } else { mu }
+  }
+
+  #[test]
+  fn test_reduce() {
+    let cycle = Cycle { mu: 3, lambda: 4 };
+    assert_eq!(
+      vec![0, 1, 2, 3, 4, 5, 6, 3, 4],
+      (0..9).map(|n| cycle.reduce(n)).collect::<Vec<_>>()
+    );
+    assert_eq!(3 + (1_000_000_000 - 3) % 4, cycle.reduce(1_000_000_000));
+  }
+
+  #[test]
+  fn test_fixed_point() {
+    let cycle = Cycle { mu: 0, lambda: 1 };
+    assert_eq!(cycle, brent(7, |&x| x));
+    assert_eq!(cycle, hashed(7, |&x| x));
+  }
+
+  proptest! {
+    #[test]
+    fn test_rho(mu in 0..200usize, lambda in 1..200usize) {
+      let expected = Cycle { mu, lambda };
+      prop_assert_eq!(expected, brent(0, rho(mu, lambda)));
+      prop_assert_eq!(expected, hashed(0, rho(mu, lambda)));
+    }
+
+    /// Any function on a finite set cycles; the detectors must agree with
+    /// each other and with stepping naively.
+    #[test]
+    fn test_random_functions(
+      next in (1..64usize).prop_flat_map(|n| prop::collection::vec(0..n, n)),
+      n in 0..10_000usize,
+    ) {
+      let f = |&x: &usize| next[x];
+      let cycle = brent(0, f);
+      prop_assert_eq!(cycle, hashed(0, f));
+
+      let naive = (0..n).fold(0, |x, _| f(&x));
+      prop_assert_eq!(naive, nth_state(0, f, n));
+      prop_assert_eq!(naive, (0..cycle.reduce(n)).fold(0, |x, _| f(&x)));
+    }
+  }
+}
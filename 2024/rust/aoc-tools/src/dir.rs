@@ -0,0 +1,317 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use anyhow::anyhow;
+use glam::{I16Vec2, I64Vec2, IVec2};
+
+/// A compass heading on a grid. As in [`crate::grid`], `y` grows downwards,
+/// so north is `(0, -1)`.
+pub trait Direction: Copy + Eq + 'static {
+  /// Every heading, clockwise from north.
+  const ALL: &'static [Self];
+
+  /// The position of this heading in [`Direction::ALL`].
+  fn index(self) -> usize;
+
+  fn ivec2(self) -> IVec2;
+
+  fn from_index(index: usize) -> Self {
+    Self::ALL[index % Self::ALL.len()]
+  }
+
+  /// The next heading clockwise.
+  fn turn_right(self) -> Self {
+    Self::from_index(self.index() + 1)
+  }
+
+  /// The next heading anticlockwise.
+  fn turn_left(self) -> Self {
+    Self::from_index(self.index() + Self::ALL.len() - 1)
+  }
+
+  fn turn_around(self) -> Self {
+    Self::from_index(self.index() + Self::ALL.len() / 2)
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction for Dir4 {
+  const ALL: &'static [Self] = &[Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+  fn index(self) -> usize {
+    self as usize
+  }
+
+  fn ivec2(self) -> IVec2 {
+    match self {
+      Dir4::North => IVec2::new(0, -1),
+      Dir4::East => IVec2::new(1, 0),
+      Dir4::South => IVec2::new(0, 1),
+      Dir4::West => IVec2::new(-1, 0),
+    }
+  }
+}
+
+impl Dir4 {
+  /// The arrow drawn for this heading: one of `^>v<`.
+  pub fn arrow(self) -> char {
+    match self {
+      Dir4::North => '^',
+      Dir4::East => '>',
+      Dir4::South => 'v',
+      Dir4::West => '<',
+    }
+  }
+
+  pub fn from_arrow(c: char) -> Option<Self> {
+    Dir4::ALL.iter().copied().find(|dir| dir.arrow() == c)
+  }
+}
+
+impl TryFrom<u8> for Dir4 {
+  type Error = anyhow::Error;
+
+  fn try_from(value: u8) -> anyhow::Result<Self> {
+    Dir4::from_arrow(value as char).ok_or_else(|| anyhow!("{:?} is not one of ^>v<", value as char))
+  }
+}
+
+/// The compass headings and the diagonals between them, so turns step by 45
+/// degrees.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction for Dir8 {
+  const ALL: &'static [Self] = &[
+    Dir8::North,
+    Dir8::NorthEast,
+    Dir8::East,
+    Dir8::SouthEast,
+    Dir8::South,
+    Dir8::SouthWest,
+    Dir8::West,
+    Dir8::NorthWest,
+  ];
+
+  fn index(self) -> usize {
+    self as usize
+  }
+
+  fn ivec2(self) -> IVec2 {
+    match self {
+      Dir8::North => IVec2::new(0, -1),
+      Dir8::NorthEast => IVec2::new(1, -1),
+      Dir8::East => IVec2::new(1, 0),
+      Dir8::SouthEast => IVec2::new(1, 1),
+      Dir8::South => IVec2::new(0, 1),
+      Dir8::SouthWest => IVec2::new(-1, 1),
+      Dir8::West => IVec2::new(-1, 0),
+      Dir8::NorthWest => IVec2::new(-1, -1),
+    }
+  }
+}
+
+impl From<Dir4> for Dir8 {
+  fn from(dir: Dir4) -> Self {
+    Dir8::ALL[dir.index() * 2]
+  }
+}
+
+macro_rules! vector_conversions {
+  ($dir:ty: $($vec:ty),*) => {
+    $(
+      impl From<$dir> for $vec {
+        fn from(dir: $dir) -> Self {
+          let v = dir.ivec2();
+          <$vec>::new(v.x as _, v.y as _)
+        }
+      }
+
+      impl TryFrom<$vec> for $dir {
+        type Error = anyhow::Error;
+
+        fn try_from(v: $vec) -> anyhow::Result<Self> {
+          <$dir>::ALL
+            .iter()
+            .copied()
+            .find(|dir| <$vec>::from(*dir) == v)
+            .ok_or_else(|| anyhow!("{} is not a unit {}", v, stringify!($dir)))
+        }
+      }
+    )*
+  };
+}
+
+vector_conversions!(Dir4: IVec2, I16Vec2, I64Vec2);
+vector_conversions!(Dir8: IVec2, I16Vec2, I64Vec2);
+
+/// A set of headings packed into a byte, e.g. to record which ways a cell has
+/// been crossed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirSet<D> {
+  bits: u8,
+  dir: PhantomData<D>,
+}
+
+impl<D: Direction> DirSet<D> {
+  pub const fn new() -> Self {
+    DirSet {
+      bits: 0,
+      dir: PhantomData,
+    }
+  }
+
+  /// Add `dir`, returning whether it was newly added.
+  pub fn insert(&mut self, dir: D) -> bool {
+    let bit = 1 << dir.index();
+    let added = self.bits & bit == 0;
+    self.bits |= bit;
+    added
+  }
+
+  pub fn remove(&mut self, dir: D) -> bool {
+    let bit = 1 << dir.index();
+    let removed = self.bits & bit != 0;
+    self.bits &= !bit;
+    removed
+  }
+
+  pub fn contains(&self, dir: D) -> bool {
+    self.bits & (1 << dir.index()) != 0
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.bits == 0
+  }
+
+  pub fn len(&self) -> usize {
+    self.bits.count_ones() as usize
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = D> + '_ {
+    D::ALL.iter().copied().filter(|dir| self.contains(*dir))
+  }
+}
+
+impl<D: Direction> Default for DirSet<D> {
+  fn default() -> Self {
+    DirSet::new()
+  }
+}
+
+impl<D: Direction> From<D> for DirSet<D> {
+  fn from(dir: D) -> Self {
+    let mut set = DirSet::new();
+    set.insert(dir);
+    set
+  }
+}
+
+impl<D: Direction> FromIterator<D> for DirSet<D> {
+  fn from_iter<I: IntoIterator<Item = D>>(iter: I) -> Self {
+    let mut set = DirSet::new();
+    for dir in iter {
+      set.insert(dir);
+    }
+    set
+  }
+}
+
+impl<D: Direction + Debug> Debug for DirSet<D> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_set().entries(self.iter()).finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::i16vec2;
+  use rstest::rstest;
+
+  use super::*;
+  use crate::grid::{NEIGHBORS_4, NEIGHBORS_8};
+
+  #[test]
+  fn test_vectors_match_neighbors() {
+    assert_eq!(
+      NEIGHBORS_4.to_vec(),
+      Dir4::ALL.iter().map(|d| d.ivec2()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+      NEIGHBORS_8.to_vec(),
+      Dir8::ALL.iter().map(|d| d.ivec2()).collect::<Vec<_>>()
+    );
+  }
+
+  #[rstest]
+  #[case(Dir4::North, Dir4::East, Dir4::West, Dir4::South)]
+  #[case(Dir4::West, Dir4::North, Dir4::South, Dir4::East)]
+  fn test_turn4(#[case] dir: Dir4, #[case] right: Dir4, #[case] left: Dir4, #[case] around: Dir4) {
+    assert_eq!(
+      (right, left, around),
+      (dir.turn_right(), dir.turn_left(), dir.turn_around())
+    );
+    // Turning right matches glam's `perp` in screen coordinates.
+    assert_eq!(IVec2::from(right), IVec2::from(dir).perp());
+  }
+
+  #[rstest]
+  #[case(Dir8::North, Dir8::NorthEast, Dir8::NorthWest, Dir8::South)]
+  #[case(Dir8::SouthWest, Dir8::West, Dir8::South, Dir8::NorthEast)]
+  fn test_turn8(#[case] dir: Dir8, #[case] right: Dir8, #[case] left: Dir8, #[case] around: Dir8) {
+    assert_eq!(
+      (right, left, around),
+      (dir.turn_right(), dir.turn_left(), dir.turn_around())
+    );
+  }
+
+  #[test]
+  fn test_conversions() -> anyhow::Result<()> {
+    assert_eq!(i16vec2(-1, 0), I16Vec2::from(Dir4::West));
+    assert_eq!(Dir4::South, Dir4::try_from(IVec2::new(0, 1))?);
+    assert!(Dir4::try_from(IVec2::new(1, 1)).is_err());
+    assert_eq!(Dir8::SouthEast, Dir8::try_from(I64Vec2::new(1, 1))?);
+    assert_eq!(Dir8::West, Dir8::from(Dir4::West));
+    assert_eq!(
+      "^>v<",
+      Dir4::ALL.iter().map(|d| d.arrow()).collect::<String>()
+    );
+    assert_eq!(Dir4::East, Dir4::try_from(b'>')?);
+    assert!(Dir4::try_from(b'x').is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_dir_set() {
+    let mut set = DirSet::from(Dir4::North);
+    assert!(set.insert(Dir4::West));
+    assert!(!set.insert(Dir4::North));
+    assert_eq!(2, set.len());
+    assert_eq!(
+      vec![Dir4::North, Dir4::West],
+      set.iter().collect::<Vec<_>>()
+    );
+    assert!(set.remove(Dir4::North));
+    assert!(!set.contains(Dir4::North));
+
+    let all = Dir8::ALL.iter().copied().collect::<DirSet<Dir8>>();
+    assert_eq!(8, all.len());
+    assert!(DirSet::<Dir8>::new().is_empty());
+  }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// An error at a position in the puzzle input, shown with the offending line:
+///
+/// ```text
+/// line 2, column 3: invalid digit found in string
+///   |
+/// 2 | 3 x
+///   |   ^
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+  /// 1-based line number, or 0 if the error couldn't be placed in the input.
+  pub line: usize,
+  /// 1-based column, counted in characters, or 0 along with `line`.
+  pub column: usize,
+  /// The text of the offending line.
+  pub snippet: String,
+  pub message: String,
+}
+
+impl ParseError {
+  /// An error at byte `offset` into `input`.
+  pub fn at(input: &str, offset: usize, message: impl Display) -> Self {
+    let mut offset = offset.min(input.len());
+    while !input.is_char_boundary(offset) {
+      offset -= 1;
+    }
+    let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let end = input[offset..]
+      .find('\n')
+      .map_or(input.len(), |i| offset + i);
+    ParseError {
+      line: input[..start].matches('\n').count() + 1,
+      column: input[start..offset].chars().count() + 1,
+      snippet: input[start..end].trim_end_matches('\r').to_owned(),
+      message: message.to_string(),
+    }
+  }
+
+  /// An error that couldn't be placed in the input.
+  pub fn unlocated(message: impl Display) -> Self {
+    ParseError {
+      line: 0,
+      column: 0,
+      snippet: String::new(),
+      message: message.to_string(),
+    }
+  }
+
+  /// An error at the start of `rest`, a suffix of `input` such as the text a
+  /// nom parser failed on.
+  pub fn at_rest(input: &str, rest: &str, message: impl Display) -> Self {
+    ParseError::at(input, input.len().saturating_sub(rest.len()), message)
+  }
+
+  /// An error at the start of `part`, a slice borrowed from `input`.
+  pub fn at_slice(input: &str, part: &str, message: impl Display) -> Self {
+    let offset = (part.as_ptr() as usize)
+      .checked_sub(input.as_ptr() as usize)
+      .filter(|&offset| offset <= input.len())
+      .unwrap_or(0);
+    ParseError::at(input, offset, message)
+  }
+
+  /// Locate a nom error from parsing `input`, or a slice of it such as one
+  /// line. An error on text outside `input` is left unlocated.
+  pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+    match err {
+      nom::Err::Incomplete(_) => ParseError::at(input, input.len(), "unexpected end of input"),
+      nom::Err::Error(e) | nom::Err::Failure(e) => {
+        let message = format!("expected {}", e.code.description());
+        match offset_within(input, e.input) {
+          Some(offset) => ParseError::at(input, offset, message),
+          None => ParseError::unlocated(message),
+        }
+      }
+    }
+  }
+}
+
+/// The byte offset of `part` in `input`, if `part` is a slice of it.
+fn offset_within(input: &str, part: &str) -> Option<usize> {
+  let start = input.as_ptr() as usize;
+  let part_start = part.as_ptr() as usize;
+  let offset = part_start.checked_sub(start)?;
+  (offset + part.len() <= input.len()).then_some(offset)
+}
+
+impl Display for ParseError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.line == 0 {
+      return write!(f, "{}", self.message);
+    }
+    let number = self.line.to_string();
+    let pad = " ".repeat(number.len());
+    // Keep tabs so the caret lines up with the snippet.
+    let indent = self
+      .snippet
+      .chars()
+      .take(self.column - 1)
+      .map(|c| if c == '\t' { '\t' } else { ' ' })
+      .collect::<String>();
+    writeln!(
+      f,
+      "line {}, column {}: {}",
+      self.line, self.column, self.message
+    )?;
+    writeln!(f, "{} |", pad)?;
+    writeln!(f, "{} | {}", number, self.snippet)?;
+    write!(f, "{} | {}^", pad, indent)
+  }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Attaches input positions to nom errors.
+pub trait NomResultExt<'a, O> {
+  /// Convert a failure into a [`ParseError`] located within `input`, the text
+  /// the parser was run on or the text it was sliced from.
+  fn located(self, input: &'a str) -> Result<(&'a str, O), ParseError>;
+}
+
+impl<'a, O> NomResultExt<'a, O> for nom::IResult<&'a str, O> {
+  fn located(self, input: &'a str) -> Result<(&'a str, O), ParseError> {
+    self.map_err(|e| ParseError::from_nom(input, e))
+  }
+}
+
+/// Parse `part`, a slice borrowed from `input`, reporting failures at its
+/// position in `input`.
+pub fn parse_at<T>(input: &str, part: &str) -> Result<T, ParseError>
+where
+  T: FromStr,
+  T::Err: Display,
+{
+  part
+    .parse()
+    .map_err(|e| ParseError::at_slice(input, part, e))
+}
+
+#[cfg(test)]
+mod tests {
+  use nom::character::complete::{char, digit1};
+  use nom::sequence::separated_pair;
+  use rstest::rstest;
+
+  use super::*;
+
+  #[rstest]
+  #[case(0, 1, 1, "ab")]
+  #[case(4, 2, 2, "cde")]
+  #[case(6, 2, 4, "cde")]
+  #[case(7, 3, 1, "")]
+  fn test_at(
+    #[case] offset: usize,
+    #[case] line: usize,
+    #[case] column: usize,
+    #[case] snippet: &str,
+  ) {
+    let err = ParseError::at("ab\ncde\n", offset, "oops");
+    assert_eq!(
+      (line, column, snippet),
+      (err.line, err.column, err.snippet.as_str())
+    );
+  }
+
+  #[test]
+  fn test_display() {
+    let input = "1 2\r\n3 x\r\n";
+    let err = parse_at::<i32>(input, &input[7..8]).unwrap_err();
+    assert_eq!(
+      "line 2, column 3: invalid digit found in string\n  |\n2 | 3 x\n  |   ^",
+      err.to_string()
+    );
+  }
+
+  #[test]
+  fn test_counts_characters() {
+    let err = ParseError::at("ñx", 2, "oops");
+    assert_eq!(2, err.column);
+  }
+
+  #[test]
+  fn test_from_nom() {
+    let input = "12,34\n56;78";
+    let line = &input[6..];
+    let err = separated_pair(digit1::<_, nom::error::Error<&str>>, char(','), digit1)(line)
+      .located(input)
+      .unwrap_err();
+    assert_eq!((2, 3), (err.line, err.column));
+    assert_eq!("expected Char", err.message);
+  }
+
+  #[test]
+  fn test_from_nom_within_line() {
+    let input = "12;34\n56,78\n";
+    let line = &input[..5];
+    let err = separated_pair(digit1::<_, nom::error::Error<&str>>, char(','), digit1)(line)
+      .located(input)
+      .unwrap_err();
+    assert_eq!((1, 3), (err.line, err.column));
+  }
+
+  #[test]
+  fn test_from_nom_outside_input() {
+    let input = "12;34\n";
+    let copy = input.to_owned();
+    let err = separated_pair(digit1::<_, nom::error::Error<&str>>, char(','), digit1)(&copy)
+      .located(input)
+      .unwrap_err();
+    assert_eq!((0, 0), (err.line, err.column));
+    assert_eq!("expected Char", err.to_string());
+  }
+}
@@ -0,0 +1,142 @@
+//! Puzzle examples kept as files, each declaring its expected answers:
+//!
+//! ```text
+//! part1: 143
+//! part2: 123
+//! ---
+//! <the example input>
+//! ```
+//!
+//! Either answer line may be left out when the example only applies to one
+//! part. [`example_tests!`](crate::example_tests) turns every
+//! `examples/<day>-<n>.txt` in a day crate into a test case per part.
+
+use std::path::Path;
+
+use anyhow::{anyhow, bail, ensure, Context};
+
+use crate::{Answer, Day, Part, Solution};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+  pub input: String,
+  pub part1: Option<Answer>,
+  pub part2: Option<Answer>,
+}
+
+impl Example {
+  pub fn parse(text: &str) -> anyhow::Result<Example> {
+    let mut example = Example {
+      input: String::new(),
+      part1: None,
+      part2: None,
+    };
+    let mut lines = text.split_inclusive('\n');
+    for line in lines.by_ref() {
+      let line = line.trim();
+      if line == "---" {
+        example.input = lines.collect();
+        return Ok(example);
+      }
+      let (key, value) = line
+        .split_once(':')
+        .ok_or_else(|| anyhow!("expected `part1: <answer>` or `---`, found {:?}", line))?;
+      let answer = Some(Answer::from(value.trim()));
+      match key.trim().parse()? {
+        Part::One => example.part1 = answer,
+        Part::Two => example.part2 = answer,
+      }
+    }
+    bail!("missing `---` line between the answers and the input")
+  }
+
+  pub fn load(path: &Path) -> anyhow::Result<Example> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    Example::parse(&text).with_context(|| format!("parse {}", path.display()))
+  }
+
+  pub fn expected(&self, part: Part) -> Option<&Answer> {
+    match part {
+      Part::One => self.part1.as_ref(),
+      Part::Two => self.part2.as_ref(),
+    }
+  }
+}
+
+/// Run `part` of `S` on the example at `path`, failing unless it gives the
+/// expected answer. Passes if the example has no answer for `part`.
+pub fn check<S: Solution>(path: &Path, part: Part) -> anyhow::Result<()> {
+  let example = Example::load(path)?;
+  let Some(expected) = example.expected(part) else {
+    return Ok(());
+  };
+  let run = Day::of::<S>().run(&example.input, &[part])?;
+  let actual = &run.parts[0].answer;
+  ensure!(
+    actual == expected,
+    "{} {}: got {}, expected {}",
+    path.display(),
+    part,
+    actual,
+    expected
+  );
+  Ok(())
+}
+
+/// Generate an rstest case per part for every `examples/<day>-<n>.txt` in the
+/// calling crate, checked with [`check`]. Takes the day's [`Solution`].
+///
+/// The crate needs `rstest` as a dev-dependency, and a build script that
+/// reruns when `examples/` changes so new files are picked up.
+#[macro_export]
+macro_rules! example_tests {
+  ($solution:ty) => {
+    #[::rstest::rstest]
+    fn example_part1(
+      #[files("examples/${CARGO_PKG_NAME}-*.txt")] path: ::std::path::PathBuf,
+    ) -> ::anyhow::Result<()> {
+      $crate::example::check::<$solution>(&path, $crate::Part::One)
+    }
+
+    #[::rstest::rstest]
+    fn example_part2(
+      #[files("examples/${CARGO_PKG_NAME}-*.txt")] path: ::std::path::PathBuf,
+    ) -> ::anyhow::Result<()> {
+      $crate::example::check::<$solution>(&path, $crate::Part::Two)
+    }
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::solution::tests::Sum;
+
+  #[test]
+  fn test_parse() -> anyhow::Result<()> {
+    let example = Example::parse("part2: abc\n---\n1 2\n3 4\n")?;
+    assert_eq!(None, example.part1);
+    assert_eq!(Some(Answer::from("abc")), example.part2);
+    assert_eq!("1 2\n3 4\n", example.input);
+
+    assert!(Example::parse("part1: 1\n1 2\n").is_err());
+    assert!(Example::parse("part3: 1\n---\n").is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_check() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("day-07-1.txt");
+    std::fs::write(&path, "part1: 10\npart2: [1, 2, 3]\n---\n1 2 3 4\n")?;
+    check::<Sum>(&path, Part::One)?;
+    let err = check::<Sum>(&path, Part::Two).unwrap_err();
+    assert!(err
+      .to_string()
+      .ends_with("part2: got [1, 2, 3, 4], expected [1, 2, 3]"));
+
+    std::fs::write(&path, "part1: 10\n---\n1 2 3 4\n")?;
+    check::<Sum>(&path, Part::Two)?;
+    Ok(())
+  }
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::input::Inputs;
+
+/// Holds the adventofcode.com `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Overrides the server inputs are fetched from.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/alaroldai/advent aoc-tools";
+const STAMP: &str = ".last-fetch";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+  /// The input was already on disk, so no request was made.
+  Cached(PathBuf),
+  Downloaded(PathBuf),
+}
+
+/// Downloads puzzle inputs into an [`Inputs`] directory, at most once per day
+/// and no more often than `min_interval`.
+#[derive(Clone)]
+pub struct Fetcher {
+  year: u32,
+  session: String,
+  base_url: String,
+  min_interval: Duration,
+}
+
+impl Fetcher {
+  pub fn new(year: u32, session: impl Into<String>) -> Self {
+    Fetcher {
+      year,
+      session: session.into(),
+      base_url: DEFAULT_BASE_URL.to_owned(),
+      min_interval: Duration::from_secs(5),
+    }
+  }
+
+  /// A fetcher configured from [`SESSION_VAR`] and [`BASE_URL_VAR`].
+  pub fn from_env(year: u32) -> anyhow::Result<Self> {
+    let session = std::env::var(SESSION_VAR).map_err(|_| {
+      anyhow!(
+        "no session token: set {} to your session cookie",
+        SESSION_VAR
+      )
+    })?;
+    let fetcher = Fetcher::new(year, session);
+    Ok(match std::env::var(BASE_URL_VAR) {
+      Ok(url) => fetcher.with_base_url(url),
+      Err(_) => fetcher,
+    })
+  }
+
+  pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+    Fetcher {
+      base_url: base_url.into().trim_end_matches('/').to_owned(),
+      ..self
+    }
+  }
+
+  pub fn with_min_interval(self, min_interval: Duration) -> Self {
+    Fetcher {
+      min_interval,
+      ..self
+    }
+  }
+
+  pub fn url(&self, day: &str) -> anyhow::Result<String> {
+    let number = day
+      .strip_prefix("day-")
+      .and_then(|n| n.parse::<u32>().ok())
+      .ok_or_else(|| anyhow!("can't fetch {:?}: expected a name like day-05", day))?;
+    Ok(format!(
+      "{}/{}/day/{}/input",
+      self.base_url, self.year, number
+    ))
+  }
+
+  pub fn fetch(&self, inputs: &Inputs, day: &str) -> anyhow::Result<Fetched> {
+    if let Ok(path) = inputs.locate(day, None) {
+      return Ok(Fetched::Cached(path));
+    }
+
+    let url = self.url(day)?;
+    fs::create_dir_all(inputs.dir())
+      .with_context(|| format!("create {}", inputs.dir().display()))?;
+    self.wait_for_rate_limit(inputs)?;
+
+    tracing::info!(%url, "fetching input");
+    let response = ureq::get(&url)
+      .set("Cookie", &format!("session={}", self.session))
+      .set("User-Agent", USER_AGENT)
+      .call();
+    fs::write(inputs.dir().join(STAMP), "")?;
+    let body = match response {
+      Ok(response) => response.into_string()?,
+      Err(ureq::Error::Status(status, response)) => {
+        let body = response.into_string().unwrap_or_default();
+        bail!(
+          "fetch {} failed with {}: {}",
+          url,
+          status,
+          body.lines().next().unwrap_or_default()
+        )
+      }
+      Err(e) => return Err(e).with_context(|| format!("fetch {}", url)),
+    };
+
+    // Write via a temporary file so an interrupted fetch never looks cached.
+    let path = inputs.path(day);
+    let partial = path.with_extension("txt.partial");
+    fs::write(&partial, body)?;
+    fs::rename(&partial, &path)?;
+    Ok(Fetched::Downloaded(path))
+  }
+
+  fn wait_for_rate_limit(&self, inputs: &Inputs) -> anyhow::Result<()> {
+    let last = match fs::metadata(inputs.dir().join(STAMP)) {
+      Ok(meta) => meta.modified()?,
+      Err(_) => return Ok(()),
+    };
+    let since = SystemTime::now().duration_since(last).unwrap_or_default();
+    if since < self.min_interval {
+      let wait = self.min_interval - since;
+      tracing::info!(?wait, "waiting before next fetch");
+      std::thread::sleep(wait);
+    }
+    Ok(())
+  }
+}
+
+/// Leaves out the session token, which would otherwise end up in logs and
+/// error messages.
+impl std::fmt::Debug for Fetcher {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Fetcher")
+      .field("year", &self.year)
+      .field("session", &"<redacted>")
+      .field("base_url", &self.base_url)
+      .field("min_interval", &self.min_interval)
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::{BufRead, BufReader, Write};
+  use std::net::TcpListener;
+  use std::sync::mpsc::{self, Receiver};
+  use std::time::Instant;
+
+  use super::*;
+
+  /// A stub server answering every request with `status` and `body`, sending
+  /// each request head back over the channel.
+  fn serve(status: &'static str, body: &'static str) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+      for stream in listener.incoming() {
+        let mut stream = stream.unwrap();
+        let mut head = String::new();
+        let mut reader = BufReader::new(&stream);
+        loop {
+          let mut line = String::new();
+          if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+            break;
+          }
+          head.push_str(&line);
+        }
+        write!(
+          stream,
+          "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+          status,
+          body.len(),
+          body
+        )
+        .unwrap();
+        if tx.send(head).is_err() {
+          break;
+        }
+      }
+    });
+    (url, rx)
+  }
+
+  fn inputs() -> (tempfile::TempDir, Inputs) {
+    let root = tempfile::tempdir().unwrap();
+    let inputs = Inputs::new(root.path()).with_dir(root.path().join("inputs"));
+    (root, inputs)
+  }
+
+  #[test]
+  fn test_fetch_then_cache() -> anyhow::Result<()> {
+    let (url, requests) = serve("200 OK", "1 2\n3 4\n");
+    let (_root, inputs) = inputs();
+    let fetcher = Fetcher::new(2024, "abc").with_base_url(url);
+
+    let path = inputs.path("day-01");
+    assert_eq!(
+      Fetched::Downloaded(path.clone()),
+      fetcher.fetch(&inputs, "day-01")?
+    );
+    assert_eq!("1 2\n3 4\n", fs::read_to_string(&path)?);
+    let head = requests.recv()?.to_lowercase();
+    assert!(head.starts_with("get /2024/day/1/input "));
+    assert!(head.contains("cookie: session=abc"));
+
+    assert_eq!(Fetched::Cached(path), fetcher.fetch(&inputs, "day-01")?);
+    assert!(requests.try_recv().is_err());
+    Ok(())
+  }
+
+  #[test]
+  fn test_rate_limit() -> anyhow::Result<()> {
+    let (url, _requests) = serve("200 OK", "input");
+    let (_root, inputs) = inputs();
+    let fetcher = Fetcher::new(2024, "abc")
+      .with_base_url(url)
+      .with_min_interval(Duration::from_millis(300));
+
+    fetcher.fetch(&inputs, "day-01")?;
+    let start = Instant::now();
+    fetcher.fetch(&inputs, "day-02")?;
+    assert!(start.elapsed() >= Duration::from_millis(250));
+    Ok(())
+  }
+
+  #[test]
+  fn test_rejected_session() {
+    let (url, _requests) = serve("400 Bad Request", "Puzzle inputs differ by user.\n");
+    let (_root, inputs) = inputs();
+    let fetcher = Fetcher::new(2024, "expired").with_base_url(url);
+
+    let err = fetcher.fetch(&inputs, "day-01").unwrap_err().to_string();
+    assert!(err.contains("400"));
+    assert!(err.contains("Puzzle inputs differ by user."));
+    assert!(!inputs.path("day-01").exists());
+  }
+
+  #[test]
+  fn test_debug_hides_session() {
+    let fetcher = Fetcher::new(2024, "53616c7465645f5f");
+    let debug = format!("{:?}", fetcher);
+    assert!(!debug.contains("53616c7465645f5f"), "{}", debug);
+    assert!(debug.contains("<redacted>"));
+  }
+}
@@ -0,0 +1,12 @@
+//! Support for the cargo-fuzz targets in the workspace's `fuzz/` directory.
+
+use crate::input::normalize_input;
+use crate::Solution;
+
+/// Hand arbitrary bytes to `S`'s parser, prepared the way the runner would.
+/// Bad input must come back as an error: libFuzzer reports any panic.
+pub fn parse<S: Solution>(data: &[u8]) {
+  if let Ok(input) = std::str::from_utf8(data) {
+    let _ = S::parse(&normalize_input(input));
+  }
+}
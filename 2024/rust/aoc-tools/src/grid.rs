@@ -0,0 +1,647 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use anyhow::{anyhow, bail};
+use glam::{ivec2, I16Vec2, I64Vec2, IVec2, U16Vec2, UVec2};
+
+use crate::ParseError;
+
+/// Offsets to the 4-connected neighbours, clockwise from north.
+pub const NEIGHBORS_4: [IVec2; 4] = [
+  IVec2::new(0, -1),
+  IVec2::new(1, 0),
+  IVec2::new(0, 1),
+  IVec2::new(-1, 0),
+];
+
+/// Offsets to the 8-connected neighbours, clockwise from north.
+pub const NEIGHBORS_8: [IVec2; 8] = [
+  IVec2::new(0, -1),
+  IVec2::new(1, -1),
+  IVec2::new(1, 0),
+  IVec2::new(1, 1),
+  IVec2::new(0, 1),
+  IVec2::new(-1, 1),
+  IVec2::new(-1, 0),
+  IVec2::new(-1, -1),
+];
+
+/// Anything that can address a grid cell as an `(x, y)` pair.
+pub trait GridIndex: Copy {
+  fn xy(self) -> (i64, i64);
+}
+
+impl GridIndex for IVec2 {
+  fn xy(self) -> (i64, i64) {
+    (self.x as i64, self.y as i64)
+  }
+}
+
+impl GridIndex for I16Vec2 {
+  fn xy(self) -> (i64, i64) {
+    (self.x as i64, self.y as i64)
+  }
+}
+
+impl GridIndex for I64Vec2 {
+  fn xy(self) -> (i64, i64) {
+    (self.x, self.y)
+  }
+}
+
+impl GridIndex for UVec2 {
+  fn xy(self) -> (i64, i64) {
+    (self.x as i64, self.y as i64)
+  }
+}
+
+impl GridIndex for U16Vec2 {
+  fn xy(self) -> (i64, i64) {
+    (self.x as i64, self.y as i64)
+  }
+}
+
+/// A dense, row-major 2D grid. `x` is the column and `y` is the row, with
+/// `(0, 0)` in the top-left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+  cells: Vec<T>,
+  width: usize,
+  height: usize,
+}
+
+impl<T> Grid<T> {
+  pub fn new(width: usize, height: usize, fill: T) -> Self
+  where
+    T: Clone,
+  {
+    Grid {
+      cells: vec![fill; width * height],
+      width,
+      height,
+    }
+  }
+
+  pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> anyhow::Result<Self> {
+    if cells.len() != width * height {
+      bail!(
+        "expected {} cells for a {}x{} grid, got {}",
+        width * height,
+        width,
+        height,
+        cells.len()
+      );
+    }
+    Ok(Grid {
+      cells,
+      width,
+      height,
+    })
+  }
+
+  /// Build a grid from rows of cells, which must all have the same length.
+  pub fn from_rows<R>(rows: impl IntoIterator<Item = R>) -> anyhow::Result<Self>
+  where
+    R: IntoIterator<Item = T>,
+  {
+    let mut cells = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+    for (y, row) in rows.into_iter().enumerate() {
+      let before = cells.len();
+      cells.extend(row);
+      let len = cells.len() - before;
+      match width {
+        None => width = Some(len),
+        Some(width) if width != len => {
+          bail!("row {} has width {}, expected {}", y, len, width)
+        }
+        _ => (),
+      }
+      height += 1;
+    }
+    Ok(Grid {
+      cells,
+      width: width.unwrap_or(0),
+      height,
+    })
+  }
+
+  pub fn width(&self) -> usize {
+    self.width
+  }
+
+  pub fn height(&self) -> usize {
+    self.height
+  }
+
+  pub fn dims(&self) -> IVec2 {
+    ivec2(self.width as i32, self.height as i32)
+  }
+
+  pub fn len(&self) -> usize {
+    self.cells.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.cells.is_empty()
+  }
+
+  pub fn cells(&self) -> &[T] {
+    &self.cells
+  }
+
+  pub fn cells_mut(&mut self) -> &mut [T] {
+    &mut self.cells
+  }
+
+  pub fn into_cells(self) -> Vec<T> {
+    self.cells
+  }
+
+  /// The offset of `pos` into [`Grid::cells`], if it is in bounds.
+  pub fn offset_of(&self, pos: impl GridIndex) -> Option<usize> {
+    let (x, y) = pos.xy();
+    if (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y) {
+      Some(y as usize * self.width + x as usize)
+    } else {
+      None
+    }
+  }
+
+  /// The position of the cell at `offset` into [`Grid::cells`].
+  pub fn position_of(&self, offset: usize) -> IVec2 {
+    ivec2((offset % self.width) as i32, (offset / self.width) as i32)
+  }
+
+  pub fn contains(&self, pos: impl GridIndex) -> bool {
+    self.offset_of(pos).is_some()
+  }
+
+  pub fn get(&self, pos: impl GridIndex) -> Option<&T> {
+    self.offset_of(pos).map(|i| &self.cells[i])
+  }
+
+  pub fn get_mut(&mut self, pos: impl GridIndex) -> Option<&mut T> {
+    self.offset_of(pos).map(|i| &mut self.cells[i])
+  }
+
+  pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+    let width = self.width as i32;
+    (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| ivec2(x, y)))
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+    self.positions().zip(self.cells.iter())
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (IVec2, &mut T)> {
+    self.positions().zip(self.cells.iter_mut())
+  }
+
+  /// The position of the first cell (in row-major order) matching `pred`.
+  pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<IVec2> {
+    self
+      .cells
+      .iter()
+      .position(pred)
+      .map(|i| self.position_of(i))
+  }
+
+  pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+    Grid {
+      cells: self.cells.iter().map(f).collect(),
+      width: self.width,
+      height: self.height,
+    }
+  }
+
+  /// In-bounds 4-connected neighbours of `pos`.
+  pub fn neighbors4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+    NEIGHBORS_4
+      .iter()
+      .map(move |d| pos + *d)
+      .filter(|p| self.contains(*p))
+  }
+
+  /// In-bounds 8-connected neighbours of `pos`.
+  pub fn neighbors8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+    NEIGHBORS_8
+      .iter()
+      .map(move |d| pos + *d)
+      .filter(|p| self.contains(*p))
+  }
+
+  /// Walk from `start` in steps of `step` until leaving the grid.
+  pub fn ray(&self, start: IVec2, step: IVec2) -> Ray<'_, T> {
+    Ray {
+      grid: self,
+      pos: start,
+      step,
+    }
+  }
+
+  pub fn row(&self, y: usize) -> &[T] {
+    &self.cells[y * self.width..(y + 1) * self.width]
+  }
+
+  pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+    // `chunks_exact` panics on a zero chunk size, so empty grids need care.
+    self.cells.chunks_exact(self.width.max(1))
+  }
+
+  /// Panics if `x` is not a column of the grid, like [`Grid::row`].
+  pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+    assert!(
+      x < self.width,
+      "column {} out of range for width {}",
+      x,
+      self.width
+    );
+    self.cells[x..].iter().step_by(self.width)
+  }
+
+  pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    (0..self.width).map(|x| self.column(x))
+  }
+
+  /// Diagonals running down and to the right, starting from the bottom-left.
+  pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    let (w, h) = (self.width as i32, self.height as i32);
+    (0..h)
+      .rev()
+      .map(|y| ivec2(0, y))
+      .chain((1..w).map(|x| ivec2(x, 0)))
+      .map(|start| self.ray(start, ivec2(1, 1)).map(|(_, t)| t))
+  }
+
+  /// Diagonals running down and to the left, starting from the top-left.
+  pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+    let (w, h) = (self.width as i32, self.height as i32);
+    (0..w)
+      .map(|x| ivec2(x, 0))
+      .chain((1..h).map(move |y| ivec2(w - 1, y)))
+      .map(|start| self.ray(start, ivec2(-1, 1)).map(|(_, t)| t))
+  }
+
+  fn remap(&self, width: usize, height: usize, source: impl Fn(i32, i32) -> IVec2) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let cells = (0..height as i32)
+      .flat_map(|y| (0..width as i32).map(move |x| (x, y)))
+      .map(|(x, y)| self[source(x, y)].clone())
+      .collect();
+    Grid {
+      cells,
+      width,
+      height,
+    }
+  }
+
+  pub fn transpose(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    self.remap(self.height, self.width, |x, y| ivec2(y, x))
+  }
+
+  /// Rotate a quarter turn clockwise.
+  pub fn rotate_cw(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let h = self.height as i32;
+    self.remap(self.height, self.width, |x, y| ivec2(y, h - 1 - x))
+  }
+
+  /// Rotate a quarter turn counter-clockwise.
+  pub fn rotate_ccw(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let w = self.width as i32;
+    self.remap(self.height, self.width, |x, y| ivec2(w - 1 - y, x))
+  }
+}
+
+impl<T, P: GridIndex> Index<P> for Grid<T> {
+  type Output = T;
+
+  fn index(&self, pos: P) -> &Self::Output {
+    match self.offset_of(pos) {
+      Some(i) => &self.cells[i],
+      None => panic!(
+        "position {:?} out of bounds for {}x{} grid",
+        pos.xy(),
+        self.width,
+        self.height
+      ),
+    }
+  }
+}
+
+impl<T, P: GridIndex> IndexMut<P> for Grid<T> {
+  fn index_mut(&mut self, pos: P) -> &mut Self::Output {
+    match self.offset_of(pos) {
+      Some(i) => &mut self.cells[i],
+      None => panic!(
+        "position {:?} out of bounds for {}x{} grid",
+        pos.xy(),
+        self.width,
+        self.height
+      ),
+    }
+  }
+}
+
+impl<T: Display> Display for Grid<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for row in self.rows() {
+      for cell in row {
+        write!(f, "{}", cell)?;
+      }
+      writeln!(f)?;
+    }
+    Ok(())
+  }
+}
+
+/// Iterator returned by [`Grid::ray`].
+pub struct Ray<'a, T> {
+  grid: &'a Grid<T>,
+  pos: IVec2,
+  step: IVec2,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+  type Item = (IVec2, &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let grid = self.grid;
+    let cell = grid.get(self.pos)?;
+    let pos = self.pos;
+    self.pos += self.step;
+    Some((pos, cell))
+  }
+}
+
+/// Coordinates of marker bytes pulled out of a grid by [`parse_with_markers`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Markers(BTreeMap<u8, Vec<IVec2>>);
+
+impl Markers {
+  /// Every occurrence of `marker`, in row-major order.
+  pub fn all(&self, marker: u8) -> &[IVec2] {
+    self.0.get(&marker).map(Vec::as_slice).unwrap_or_default()
+  }
+
+  /// The first occurrence of `marker`, if any.
+  pub fn get(&self, marker: u8) -> Option<IVec2> {
+    self.all(marker).first().copied()
+  }
+
+  /// The position of a marker that must appear exactly once.
+  pub fn one(&self, marker: u8) -> anyhow::Result<IVec2> {
+    match self.all(marker) {
+      [pos] => Ok(*pos),
+      [] => Err(anyhow!("no '{}' marker in grid", marker as char)),
+      all => Err(anyhow!(
+        "expected one '{}' marker in grid, found {}",
+        marker as char,
+        all.len()
+      )),
+    }
+  }
+}
+
+/// Parse a grid of puzzle text, mapping each byte with `cell`.
+///
+/// Blank lines around the grid are ignored, and every remaining line must have
+/// the same width.
+pub fn parse<T>(input: &str, cell: impl Fn(u8) -> anyhow::Result<T>) -> anyhow::Result<Grid<T>> {
+  parse_inner(input, |_, b| cell(b))
+}
+
+/// Like [`parse`], but any byte in `markers` has its position recorded and is
+/// replaced with `floor` in the grid.
+pub fn parse_with_markers<T: Clone>(
+  input: &str,
+  markers: &[u8],
+  floor: T,
+  cell: impl Fn(u8) -> anyhow::Result<T>,
+) -> anyhow::Result<(Grid<T>, Markers)> {
+  let mut found = Markers::default();
+  let grid = parse_inner(input, |pos, b| {
+    if markers.contains(&b) {
+      found.0.entry(b).or_default().push(pos);
+      Ok(floor.clone())
+    } else {
+      cell(b)
+    }
+  })?;
+  Ok((grid, found))
+}
+
+fn parse_inner<T>(
+  input: &str,
+  mut cell: impl FnMut(IVec2, u8) -> anyhow::Result<T>,
+) -> anyhow::Result<Grid<T>> {
+  let lines = input
+    .lines()
+    .skip_while(|line| line.trim().is_empty())
+    .collect::<Vec<_>>();
+  let end = lines
+    .iter()
+    .rposition(|line| !line.trim().is_empty())
+    .map_or(0, |i| i + 1);
+
+  let mut cells = Vec::new();
+  let width = lines.first().map_or(0, |line| line.len());
+  for (y, line) in lines[..end].iter().enumerate() {
+    if line.len() != width {
+      let at = &line[line.floor_char_boundary(width)..];
+      let message = format!("line is {} bytes wide, expected {}", line.len(), width);
+      return Err(ParseError::at_slice(input, at, message).into());
+    }
+    for (x, b) in line.bytes().enumerate() {
+      let value = cell(ivec2(x as i32, y as i32), b).map_err(|e| {
+        ParseError::at_slice(
+          input,
+          &line[line.floor_char_boundary(x)..],
+          format!("{:#}", e),
+        )
+      })?;
+      cells.push(value);
+    }
+  }
+  Grid::from_vec(width, end, cells)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{i16vec2, u16vec2};
+  use rstest::rstest;
+
+  use super::*;
+
+  fn letters() -> Grid<char> {
+    Grid::from_rows(["abc".chars(), "def".chars()]).unwrap()
+  }
+
+  fn collect<'a>(it: impl Iterator<Item = impl Iterator<Item = &'a char>>) -> Vec<String> {
+    it.map(|i| i.collect()).collect()
+  }
+
+  #[test]
+  fn test_from_rows_rejects_ragged() {
+    assert!(Grid::from_rows(["abc".chars(), "de".chars()]).is_err());
+  }
+
+  #[test]
+  fn test_from_vec_checks_len() {
+    assert!(Grid::from_vec(2, 2, vec![0; 3]).is_err());
+    assert!(Grid::from_vec(2, 2, vec![0; 4]).is_ok());
+  }
+
+  #[rstest]
+  #[case(ivec2(0, 0), Some('a'))]
+  #[case(ivec2(2, 1), Some('f'))]
+  #[case(ivec2(3, 0), None)]
+  #[case(ivec2(0, 2), None)]
+  #[case(ivec2(-1, 0), None)]
+  fn test_get(#[case] pos: IVec2, #[case] expected: Option<char>) {
+    assert_eq!(expected, letters().get(pos).cloned());
+  }
+
+  #[test]
+  fn test_index_types() {
+    let grid = letters();
+    assert_eq!('e', grid[ivec2(1, 1)]);
+    assert_eq!('e', grid[i16vec2(1, 1)]);
+    assert_eq!('e', grid[u16vec2(1, 1)]);
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_index_out_of_bounds() {
+    let _ = letters()[ivec2(5, 5)];
+  }
+
+  #[rstest]
+  #[case(3)]
+  #[case(5)]
+  #[should_panic(expected = "out of range")]
+  fn test_column_out_of_bounds(#[case] x: usize) {
+    let _ = letters().column(x);
+  }
+
+  #[test]
+  #[should_panic(expected = "out of range")]
+  fn test_column_of_empty_grid() {
+    let _ = Grid::<u8>::new(0, 0, 0).column(0);
+  }
+
+  #[test]
+  fn test_neighbors() {
+    let grid = letters();
+    assert_eq!(
+      vec![ivec2(0, 0), ivec2(1, 1)],
+      grid.neighbors4(ivec2(0, 1)).collect::<Vec<_>>()
+    );
+    assert_eq!(5, grid.neighbors8(ivec2(1, 0)).count());
+  }
+
+  #[test]
+  fn test_lines() {
+    let grid = letters();
+    assert_eq!(vec!["abc", "def"], collect(grid.rows().map(|r| r.iter())));
+    assert_eq!(vec!["ad", "be", "cf"], collect(grid.columns()));
+    assert_eq!(vec!["d", "ae", "bf", "c"], collect(grid.diagonals()));
+    assert_eq!(vec!["a", "bd", "ce", "f"], collect(grid.anti_diagonals()));
+  }
+
+  #[test]
+  fn test_ray() {
+    let grid = letters();
+    assert_eq!(
+      vec![
+        (ivec2(2, 1), &'f'),
+        (ivec2(1, 1), &'e'),
+        (ivec2(0, 1), &'d')
+      ],
+      grid.ray(ivec2(2, 1), ivec2(-1, 0)).collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn test_transforms() {
+    let grid = letters();
+    assert_eq!("ad\nbe\ncf\n", grid.transpose().to_string());
+    assert_eq!("da\neb\nfc\n", grid.rotate_cw().to_string());
+    assert_eq!("cf\nbe\nad\n", grid.rotate_ccw().to_string());
+    assert_eq!(grid, grid.rotate_cw().rotate_ccw());
+  }
+
+  #[test]
+  fn test_position() {
+    let grid = letters();
+    assert_eq!(Some(ivec2(1, 1)), grid.position(|c| *c == 'e'));
+    assert_eq!(
+      ivec2(1, 1),
+      grid.position_of(grid.offset_of(ivec2(1, 1)).unwrap())
+    );
+  }
+
+  fn cell(b: u8) -> anyhow::Result<bool> {
+    match b {
+      b'.' => Ok(false),
+      b'#' => Ok(true),
+      _ => Err(anyhow!("unexpected {:?}", b as char)),
+    }
+  }
+
+  #[test]
+  fn test_parse_trims_blank_lines() -> anyhow::Result<()> {
+    let grid = parse("\n\n.#\r\n#.\n\n", cell)?;
+    assert_eq!(ivec2(2, 2), grid.dims());
+    assert_eq!(vec![false, true, true, false], grid.into_cells());
+    Ok(())
+  }
+
+  #[rstest]
+  #[case("..\n...\n", (2, 3), "line is 3 bytes wide, expected 2")]
+  #[case("\n..\n.x\n", (3, 2), "unexpected 'x'")]
+  fn test_parse_errors(
+    #[case] input: &str,
+    #[case] position: (usize, usize),
+    #[case] message: &str,
+  ) {
+    let err = parse(input, cell).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(position, (err.line, err.column));
+    assert_eq!(message, err.message);
+  }
+
+  #[test]
+  fn test_parse_error_inside_character() {
+    let err = parse("..\n.é.\n", cell).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((2, 2), (err.line, err.column));
+    assert_eq!("line is 4 bytes wide, expected 2", err.message);
+  }
+
+  #[test]
+  fn test_parse_with_markers() -> anyhow::Result<()> {
+    let (grid, markers) = parse_with_markers("S.#\n.#E\n", b"SE", false, cell)?;
+    assert_eq!(
+      "..#\n.#.\n",
+      grid.map(|c| if *c { '#' } else { '.' }).to_string()
+    );
+    assert_eq!(ivec2(0, 0), markers.one(b'S')?);
+    assert_eq!(Some(ivec2(2, 1)), markers.get(b'E'));
+    assert!(markers.one(b'^').is_err());
+    Ok(())
+  }
+}
@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+use crate::Part;
+
+/// Overrides the directory puzzle inputs are read from.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Finds puzzle inputs on disk at runtime.
+///
+/// A day's input lives at `<dir>/<day>.txt`, where `<dir>` defaults to
+/// `inputs/` in the workspace. Inputs dropped next to the day crate as
+/// `input1.txt`/`input2.txt` are still picked up.
+#[derive(Clone, Debug)]
+pub struct Inputs {
+  root: PathBuf,
+  dir: PathBuf,
+}
+
+impl Inputs {
+  /// Inputs for the workspace at `root`, honouring [`INPUT_DIR_VAR`].
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    let root = root.into();
+    let dir = std::env::var_os(INPUT_DIR_VAR)
+      .map(PathBuf::from)
+      .unwrap_or_else(|| root.join("inputs"));
+    Inputs { root, dir }
+  }
+
+  /// Inputs for the workspace containing the crate at `manifest_dir`,
+  /// usually `env!("CARGO_MANIFEST_DIR")`.
+  pub fn for_crate(manifest_dir: &str) -> Self {
+    let manifest_dir = Path::new(manifest_dir);
+    Inputs::new(manifest_dir.parent().unwrap_or(manifest_dir))
+  }
+
+  pub fn with_dir(self, dir: impl Into<PathBuf>) -> Self {
+    Inputs {
+      dir: dir.into(),
+      ..self
+    }
+  }
+
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// The path a day's input should be saved to.
+  pub fn path(&self, day: &str) -> PathBuf {
+    self.dir.join(format!("{}.txt", day))
+  }
+
+  /// Find the input for `day`, preferring the copy for `part` when falling
+  /// back to the per-crate files.
+  pub fn locate(&self, day: &str, part: Option<Part>) -> anyhow::Result<PathBuf> {
+    let expected = self.path(day);
+    let legacy = match part {
+      Some(Part::Two) => ["input2.txt", "input1.txt"],
+      _ => ["input1.txt", "input2.txt"],
+    }
+    .map(|name| self.root.join(day).join(name));
+
+    if let Some(path) = std::iter::once(&expected)
+      .chain(&legacy)
+      .find(|path| path.is_file())
+    {
+      return Ok(path.clone());
+    }
+    bail!(
+      "no puzzle input for {}: expected it at {} (or {})",
+      day,
+      expected.display(),
+      legacy[0].display()
+    )
+  }
+
+  pub fn read(&self, day: &str, part: Option<Part>) -> anyhow::Result<String> {
+    let path = self.locate(day, part)?;
+    std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))
+  }
+}
+
+/// Strip the indentation shared by every non-blank line of `input`, so
+/// examples can be indented along with the test that holds them.
+pub fn dedent(input: &str) -> String {
+  dedent_lines(&input.lines().collect::<Vec<_>>())
+}
+
+/// Bring input into the shape parsers expect: LF line endings, no blank lines
+/// at either end, no common indentation (see [`dedent`]), and a final newline.
+///
+/// Leading whitespace that every line shares is lost, so a puzzle where it
+/// matters would need to parse the raw input.
+pub fn normalize_input(input: &str) -> String {
+  let lines = input.lines().collect::<Vec<_>>();
+  let is_blank = |line: &&str| line.trim().is_empty();
+  match (
+    lines.iter().position(|line| !is_blank(line)),
+    lines.iter().rposition(|line| !is_blank(line)),
+  ) {
+    (Some(first), Some(last)) => dedent_lines(&lines[first..=last]),
+    _ => String::new(),
+  }
+}
+
+/// Lines holding only whitespace come out empty.
+fn dedent_lines(lines: &[&str]) -> String {
+  let indent = lines
+    .iter()
+    .filter(|line| !line.trim().is_empty())
+    .map(|line| &line[..line.len() - line.trim_start().len()])
+    .reduce(|a, b| {
+      let common = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(a, b)| a == b)
+        .map(|(a, _)| a.len_utf8())
+        .sum();
+      &a[..common]
+    })
+    .unwrap_or("");
+
+  let mut out = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+  for line in lines {
+    if !line.trim().is_empty() {
+      out.push_str(&line[indent.len()..]);
+    }
+    out.push('\n');
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use rstest::rstest;
+
+  use super::*;
+
+  fn workspace() -> anyhow::Result<tempfile::TempDir> {
+    let root = tempfile::tempdir()?;
+    fs::create_dir_all(root.path().join("day-01"))?;
+    Ok(root)
+  }
+
+  #[test]
+  fn test_prefers_input_dir() -> anyhow::Result<()> {
+    let root = workspace()?;
+    let inputs = Inputs::new(root.path()).with_dir(root.path().join("inputs"));
+    fs::create_dir_all(inputs.dir())?;
+    fs::write(inputs.path("day-01"), "shared")?;
+    fs::write(root.path().join("day-01/input1.txt"), "legacy")?;
+
+    assert_eq!("shared", inputs.read("day-01", Some(Part::One))?);
+    Ok(())
+  }
+
+  #[test]
+  fn test_falls_back_to_crate_inputs() -> anyhow::Result<()> {
+    let root = workspace()?;
+    let inputs = Inputs::new(root.path()).with_dir(root.path().join("inputs"));
+    fs::write(root.path().join("day-01/input1.txt"), "one")?;
+
+    assert_eq!("one", inputs.read("day-01", Some(Part::One))?);
+    assert_eq!("one", inputs.read("day-01", Some(Part::Two))?);
+    fs::write(root.path().join("day-01/input2.txt"), "two")?;
+    assert_eq!("two", inputs.read("day-01", Some(Part::Two))?);
+    Ok(())
+  }
+
+  #[test]
+  fn test_missing_input_names_path() -> anyhow::Result<()> {
+    let root = workspace()?;
+    let inputs = Inputs::new(root.path()).with_dir(root.path().join("inputs"));
+    let err = inputs.read("day-01", None).unwrap_err().to_string();
+    assert!(err.contains(&inputs.path("day-01").display().to_string()));
+    Ok(())
+  }
+
+  #[rstest]
+  #[case("a\n  b\n", "a\n  b\n")]
+  #[case("  a\n    b\n", "a\n  b\n")]
+  #[case("  a\n\n  b", "a\n\nb\n")]
+  #[case("\t.#\n\t#.\n", ".#\n#.\n")]
+  fn test_dedent(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, dedent(input));
+  }
+
+  #[rstest]
+  #[case("3   4\n4   3\n", "3   4\n4   3\n")]
+  #[case("3   4\r\n4   3", "3   4\n4   3\n")]
+  #[case("\n      ..#\n      #..\n    ", "..#\n#..\n")]
+  #[case("\n  47|53\n  \n  75,47\n\n", "47|53\n\n75,47\n")]
+  #[case(" \n\n", "")]
+  fn test_normalize_input(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, normalize_input(input));
+  }
+}
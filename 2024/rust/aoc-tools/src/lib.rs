@@ -1 +1,22 @@
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+pub mod dir;
+pub mod error;
+pub mod example;
+pub mod fetch;
+pub mod fuzz;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod search;
+pub mod sim;
+pub mod solution;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+pub mod variant;
 
+pub use answer::Answer;
+pub use error::ParseError;
+pub use solution::{Day, Part, Solution};
@@ -0,0 +1,270 @@
+//! nom combinators for common puzzle input shapes.
+
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use nom::bytes::complete::take_while_m_n;
+use nom::character::complete::{digit1, line_ending, multispace0, one_of, space0};
+use nom::combinator::{map_res, opt, recognize};
+use nom::error::Error;
+use nom::multi::{many1_count, separated_list1};
+use nom::sequence::{delimited, pair, tuple};
+use nom::{IResult, Parser};
+
+use crate::error::{NomResultExt, ParseError};
+
+/// An unsigned decimal integer of any width.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+  map_res(digit1, str::parse)(input)
+}
+
+/// A decimal integer of any width, with an optional leading sign.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+  map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// An unsigned integer written with between `min` and `max` digits.
+pub fn digits<T: FromStr>(min: usize, max: usize) -> impl FnMut(&str) -> IResult<&str, T> {
+  move |input: &str| {
+    map_res(
+      take_while_m_n(min, max, |c: char| c.is_ascii_digit()),
+      str::parse,
+    )(input)
+  }
+}
+
+/// `f`, surrounded by optional spaces and tabs.
+pub fn ws<'a, O, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, O>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  delimited(space0, f, space0)
+}
+
+/// One or more lines, each parsed with `f`. The final line ending is left
+/// unconsumed.
+pub fn lines<'a, O, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  separated_list1(line_ending, f)
+}
+
+/// Like [`lines`], but tolerating indentation, trailing spaces and blank
+/// lines anywhere, as in indented test fixtures.
+pub fn lines_ws<'a, O, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  delimited(
+    multispace0,
+    separated_list1(tuple((space0, line_ending, multispace0)), f),
+    multispace0,
+  )
+}
+
+/// The break between two sections: a line ending, then one or more blank
+/// lines.
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+  recognize(pair(line_ending, many1_count(pair(space0, line_ending))))(input)
+}
+
+/// One or more sections separated by blank lines, each parsed with `f`.
+pub fn sections<'a, O, F>(f: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+  F: Parser<&'a str, O, Error<&'a str>>,
+{
+  separated_list1(blank_line, f)
+}
+
+/// Run `parser` over `input`, which it must consume entirely apart from
+/// trailing whitespace.
+pub fn parse_all<'a, O>(
+  input: &'a str,
+  mut parser: impl Parser<&'a str, O, Error<&'a str>>,
+) -> Result<O, ParseError> {
+  let (rest, out) = parser.parse(input).located(input)?;
+  let rest = rest.trim_start();
+  if !rest.is_empty() {
+    return Err(ParseError::at_rest(
+      input,
+      rest,
+      "unexpected trailing input",
+    ));
+  }
+  Ok(out)
+}
+
+/// Find every match of `parser` anywhere in `input`, yielding each with its
+/// byte offset. Text the parser rejects is skipped a character at a time.
+pub fn scan_matches<'a, O, P>(parser: P, input: &'a str) -> Scan<'a, O, P>
+where
+  P: Parser<&'a str, O, Error<&'a str>>,
+{
+  Scan {
+    input,
+    pos: 0,
+    parser,
+    overlapping: false,
+    first: None,
+    output: PhantomData,
+  }
+}
+
+/// The iterator returned by [`scan_matches`].
+pub struct Scan<'a, O, P> {
+  input: &'a str,
+  pos: usize,
+  parser: P,
+  overlapping: bool,
+  first: Option<&'a [char]>,
+  output: PhantomData<fn() -> O>,
+}
+
+impl<'a, O, P> Scan<'a, O, P> {
+  /// Also yield matches that start inside an earlier match.
+  pub fn overlapping(self) -> Self {
+    Scan {
+      overlapping: true,
+      ..self
+    }
+  }
+
+  /// Only try the parser where the text starts with one of `first`, jumping
+  /// straight between candidates.
+  pub fn starting_with(self, first: &'a [char]) -> Self {
+    Scan {
+      first: Some(first),
+      ..self
+    }
+  }
+
+  fn step(&self, pos: usize) -> usize {
+    pos + self.input[pos..].chars().next().map_or(1, char::len_utf8)
+  }
+}
+
+impl<'a, O, P> Iterator for Scan<'a, O, P>
+where
+  P: Parser<&'a str, O, Error<&'a str>>,
+{
+  type Item = (usize, O);
+
+  fn next(&mut self) -> Option<(usize, O)> {
+    while self.pos < self.input.len() {
+      if let Some(first) = self.first {
+        self.pos += self.input[self.pos..].find(first)?;
+      }
+      let start = self.pos;
+      match self.parser.parse(&self.input[start..]) {
+        Ok((rest, value)) => {
+          let end = self.input.len() - rest.len();
+          self.pos = if self.overlapping || end == start {
+            self.step(start)
+          } else {
+            end
+          };
+          return Some((start, value));
+        }
+        Err(_) => self.pos = self.step(start),
+      }
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use nom::bytes::complete::tag;
+  use nom::character::complete::{alpha1, char};
+
+  use super::*;
+
+  #[test]
+  fn test_integers() {
+    assert_eq!(Ok(("x", 123u8)), unsigned::<u8>("123x"));
+    assert!(unsigned::<u8>("256").is_err());
+    assert!(unsigned::<u32>("-1").is_err());
+    assert_eq!(Ok(("", -42i64)), signed::<i64>("-42"));
+    assert_eq!(Ok(("", 7i16)), signed::<i16>("+7"));
+    assert_eq!(
+      Ok(("", u128::MAX)),
+      unsigned::<u128>("340282366920938463463374607431768211455")
+    );
+  }
+
+  #[test]
+  fn test_digits() {
+    assert_eq!(Ok(("4", 123)), digits::<u32>(1, 3)("1234"));
+    assert!(digits::<u32>(2, 3)("1,").is_err());
+  }
+
+  #[test]
+  fn test_sections() -> anyhow::Result<()> {
+    let input = "1|2\n3|4\n\n  \n5,6\n7\n";
+    let section = lines(separated_list1(one_of("|,"), unsigned::<u32>));
+    assert_eq!(
+      vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7]]],
+      parse_all(input, sections(section))?
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn test_lines_ws() -> anyhow::Result<()> {
+    let input = "
+      1 2
+      3   4
+    ";
+    let row = pair(ws(unsigned::<u32>), unsigned::<u32>);
+    assert_eq!(vec![(1, 2), (3, 4)], parse_all(input, lines_ws(row))?);
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_all_rejects_trailing_input() {
+    let err = parse_all(
+      "1,2\n3;4\n",
+      lines(separated_list1(char(','), unsigned::<u32>)),
+    )
+    .unwrap_err();
+    assert_eq!((2, 2), (err.line, err.column));
+  }
+
+  #[test]
+  fn test_scan_matches() {
+    let input = "é1,x22,333";
+    assert_eq!(
+      vec![(2, 1), (5, 22), (8, 333)],
+      scan_matches(unsigned::<u32>, input).collect::<Vec<_>>()
+    );
+    assert_eq!(
+      vec![(5, 22), (8, 333)],
+      scan_matches(digits::<u32>(2, 3), input).collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn test_scan_matches_overlapping() {
+    let input = "aaaa";
+    assert_eq!(2, scan_matches(tag("aa"), input).count());
+    assert_eq!(
+      vec![0, 1, 2],
+      scan_matches(tag("aa"), input)
+        .overlapping()
+        .map(|(offset, _)| offset)
+        .collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn test_scan_matches_starting_with() {
+    let input = "xyz do dont ñdo";
+    assert_eq!(
+      vec![(4, "do"), (7, "dont"), (14, "do")],
+      scan_matches(alpha1, input)
+        .starting_with(&['d'])
+        .collect::<Vec<_>>()
+    );
+  }
+}
@@ -0,0 +1,358 @@
+//! Shortest paths over implicit graphs, given as a neighbour function.
+//!
+//! Every search takes any number of start states and an `is_goal` predicate,
+//! and stops at the first goal it settles; pass `|_| false` to explore
+//! everything reachable.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use glam::IVec2;
+use num_traits::{One, Zero};
+
+use crate::grid::Grid;
+
+/// An edge cost.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Zero {}
+
+impl<C: Copy + Ord + Add<Output = C> + Zero> Cost for C {}
+
+/// The result of a search: the distance to, and predecessor of, every state it
+/// reached.
+///
+/// If the search stopped at a goal, distances to states it had not yet settled
+/// are upper bounds.
+#[derive(Clone, Debug)]
+pub struct Paths<S, C> {
+  distances: HashMap<S, C>,
+  predecessors: HashMap<S, S>,
+  goal: Option<S>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Paths<S, C> {
+  fn new(starts: impl IntoIterator<Item = S>, zero: C) -> Self {
+    Paths {
+      distances: starts.into_iter().map(|s| (s, zero)).collect(),
+      predecessors: HashMap::new(),
+      goal: None,
+    }
+  }
+
+  /// The goal the search stopped at, if it reached one.
+  pub fn goal(&self) -> Option<&S> {
+    self.goal.as_ref()
+  }
+
+  pub fn distance(&self, state: &S) -> Option<C> {
+    self.distances.get(state).copied()
+  }
+
+  /// The distance to the goal the search stopped at.
+  pub fn goal_distance(&self) -> Option<C> {
+    self.goal.as_ref().and_then(|goal| self.distance(goal))
+  }
+
+  pub fn distances(&self) -> &HashMap<S, C> {
+    &self.distances
+  }
+
+  pub fn predecessors(&self) -> &HashMap<S, S> {
+    &self.predecessors
+  }
+
+  /// The states along a shortest path from one of the starts to `state`,
+  /// inclusive.
+  pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+    if !self.distances.contains_key(state) {
+      return None;
+    }
+    let mut path = vec![state.clone()];
+    while let Some(prev) = self.predecessors.get(path.last().unwrap()) {
+      path.push(prev.clone());
+    }
+    path.reverse();
+    Some(path)
+  }
+
+  /// The path to the goal the search stopped at.
+  pub fn path(&self) -> Option<Vec<S>> {
+    self.goal.as_ref().and_then(|goal| self.path_to(goal))
+  }
+
+  fn relax(&mut self, from: &S, to: &S, cost: C) -> bool
+  where
+    C: Ord,
+  {
+    if self.distance(to).is_some_and(|known| known <= cost) {
+      return false;
+    }
+    self.distances.insert(to.clone(), cost);
+    self.predecessors.insert(to.clone(), from.clone());
+    true
+  }
+}
+
+/// Breadth-first search, where every step costs 1.
+pub fn bfs<S, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbors: impl FnMut(&S) -> I,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, usize>
+where
+  S: Clone + Eq + Hash,
+  I: IntoIterator<Item = S>,
+{
+  let mut queue = starts.into_iter().collect::<VecDeque<_>>();
+  let mut paths = Paths::new(queue.iter().cloned(), 0);
+  while let Some(state) = queue.pop_front() {
+    if is_goal(&state) {
+      paths.goal = Some(state);
+      break;
+    }
+    let next_cost = paths.distances[&state] + 1;
+    for next in neighbors(&state) {
+      if !paths.distances.contains_key(&next) {
+        paths.relax(&state, &next, next_cost);
+        queue.push_back(next);
+      }
+    }
+  }
+  paths
+}
+
+/// BFS for graphs whose edges cost either 0 or 1.
+///
+/// # Panics
+///
+/// If an edge costs anything else.
+pub fn bfs01<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbors: impl FnMut(&S) -> I,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Cost + One,
+  I: IntoIterator<Item = (S, C)>,
+{
+  let mut queue = starts
+    .into_iter()
+    .map(|s| (C::zero(), s))
+    .collect::<VecDeque<_>>();
+  let mut paths = Paths::new(queue.iter().map(|(_, s)| s.clone()), C::zero());
+  while let Some((cost, state)) = queue.pop_front() {
+    if paths.distance(&state).is_some_and(|known| known < cost) {
+      continue;
+    }
+    if is_goal(&state) {
+      paths.goal = Some(state);
+      break;
+    }
+    for (next, step) in neighbors(&state) {
+      if !paths.relax(&state, &next, cost + step) {
+        continue;
+      }
+      if step.is_zero() {
+        queue.push_front((cost, next));
+      } else if step.is_one() {
+        queue.push_back((cost + step, next));
+      } else {
+        panic!("bfs01 edges must cost 0 or 1");
+      }
+    }
+  }
+  paths
+}
+
+/// Dijkstra's algorithm, for non-negative edge costs.
+pub fn dijkstra<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  neighbors: impl FnMut(&S) -> I,
+  is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Cost,
+  I: IntoIterator<Item = (S, C)>,
+{
+  astar(starts, neighbors, |_| C::zero(), is_goal)
+}
+
+/// A* search. `heuristic` estimates the remaining cost to a goal, and must
+/// never overestimate it.
+pub fn astar<S, C, I>(
+  starts: impl IntoIterator<Item = S>,
+  mut neighbors: impl FnMut(&S) -> I,
+  mut heuristic: impl FnMut(&S) -> C,
+  mut is_goal: impl FnMut(&S) -> bool,
+) -> Paths<S, C>
+where
+  S: Clone + Eq + Hash,
+  C: Cost,
+  I: IntoIterator<Item = (S, C)>,
+{
+  let mut queue = starts
+    .into_iter()
+    .map(|state| Queued {
+      priority: heuristic(&state),
+      cost: C::zero(),
+      state,
+    })
+    .collect::<BinaryHeap<_>>();
+  let mut paths = Paths::new(queue.iter().map(|q| q.state.clone()), C::zero());
+  while let Some(Queued { cost, state, .. }) = queue.pop() {
+    if paths.distance(&state).is_some_and(|known| known < cost) {
+      continue;
+    }
+    if is_goal(&state) {
+      paths.goal = Some(state);
+      break;
+    }
+    for (next, step) in neighbors(&state) {
+      let cost = cost + step;
+      if paths.relax(&state, &next, cost) {
+        queue.push(Queued {
+          priority: cost + heuristic(&next),
+          cost,
+          state: next,
+        });
+      }
+    }
+  }
+  paths
+}
+
+/// A [`BinaryHeap`] entry, ordered so the lowest priority pops first.
+struct Queued<S, C> {
+  priority: C,
+  cost: C,
+  state: S,
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+  fn eq(&self, other: &Self) -> bool {
+    self.priority == other.priority
+  }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+  fn cmp(&self, other: &Self) -> Ordering {
+    other.priority.cmp(&self.priority)
+  }
+}
+
+/// Neighbours for 4-way movement between the cells of `grid` that `passable`
+/// accepts, for any of the searches.
+pub fn grid_steps4<'a, T>(
+  grid: &'a Grid<T>,
+  passable: impl Fn(&T) -> bool + Copy + 'a,
+) -> impl Fn(&IVec2) -> std::vec::IntoIter<IVec2> + 'a {
+  move |pos: &IVec2| {
+    grid
+      .neighbors4(*pos)
+      .filter(|next| passable(&grid[*next]))
+      .collect::<Vec<_>>()
+      .into_iter()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::ivec2;
+
+  use super::*;
+  use crate::grid;
+
+  fn maze() -> Grid<bool> {
+    let input = "S...#
+.##.#
+...#.
+#.#..
+...#E";
+    grid::parse(input, |b| Ok(b != b'#')).unwrap()
+  }
+
+  #[test]
+  fn test_bfs_grid() {
+    let maze = maze();
+    let paths = bfs([ivec2(0, 0)], grid_steps4(&maze, |open| *open), |_| false);
+    assert_eq!(Some(6), paths.distance(&ivec2(2, 4)));
+    assert_eq!(None, paths.distance(&ivec2(4, 4)));
+    assert_eq!(None, paths.goal());
+    assert_eq!(
+      Some(vec![ivec2(0, 0), ivec2(0, 1), ivec2(0, 2), ivec2(1, 2)]),
+      paths.path_to(&ivec2(1, 2))
+    );
+  }
+
+  #[test]
+  fn test_bfs_multiple_sources_and_goals() {
+    let maze = maze();
+    let paths = bfs(
+      [ivec2(0, 0), ivec2(3, 0)],
+      grid_steps4(&maze, |open| *open),
+      |pos| [ivec2(2, 0), ivec2(0, 4)].contains(pos),
+    );
+    assert_eq!(Some(&ivec2(2, 0)), paths.goal());
+    assert_eq!(Some(1), paths.goal_distance());
+    assert_eq!(Some(vec![ivec2(3, 0), ivec2(2, 0)]), paths.path());
+  }
+
+  /// Edges from node `n` to `n + 1` (cost 1) and `n * 2` (cost 0 for even
+  /// `n`, else 1), up to 64.
+  fn edges(n: &u32) -> Vec<(u32, u32)> {
+    [(n + 1, 1), (n * 2, n % 2)]
+      .into_iter()
+      .filter(|(m, _)| *m <= 64 && m != n)
+      .collect()
+  }
+
+  #[test]
+  fn test_weighted_searches_agree() {
+    let dijkstra = dijkstra([1], edges, |_| false);
+    let bfs01 = bfs01([1], edges, |_| false);
+    assert_eq!(dijkstra.distances(), bfs01.distances());
+    assert_eq!(Some(1), dijkstra.distance(&64));
+    assert_eq!(Some(1), dijkstra.distance(&4));
+
+    let astar = astar([1], edges, |_| 0, |n| *n == 48);
+    assert_eq!(Some(dijkstra.distance(&48).unwrap()), astar.goal_distance());
+    let path = astar.path().unwrap();
+    assert_eq!((Some(&1), Some(&48)), (path.first(), path.last()));
+  }
+
+  #[test]
+  fn test_astar_grid() {
+    let maze = maze();
+    let goal = ivec2(4, 2);
+    let manhattan = |pos: &IVec2| (goal - *pos).abs().element_sum();
+    let steps = grid_steps4(&maze, |open| *open);
+    let paths = astar(
+      [ivec2(0, 0)],
+      |pos| steps(pos).map(|next| (next, 1)),
+      manhattan,
+      |pos| *pos == goal,
+    );
+    assert_eq!(None, paths.goal());
+
+    let goal = ivec2(2, 4);
+    let paths = astar(
+      [ivec2(0, 0)],
+      |pos| steps(pos).map(|next| (next, 1)),
+      |pos| (goal - *pos).abs().element_sum(),
+      |pos| *pos == goal,
+    );
+    assert_eq!(Some(6), paths.goal_distance());
+    assert_eq!(7, paths.path().unwrap().len());
+  }
+}
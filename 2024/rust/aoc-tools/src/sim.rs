@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use crate::dir::{DirSet, Direction};
+use crate::grid::{Grid, GridIndex};
+
+/// What a simulation does after one step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step<S, E> {
+  Continue(S),
+  Exit(E),
+}
+
+/// How a simulation ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome<S, E> {
+  /// The step rule returned [`Step::Exit`].
+  Exit(E),
+  /// The simulation reached this state a second time, so it never ends.
+  Loop(S),
+}
+
+/// Remembers the states a simulation has been in.
+pub trait Visited<S> {
+  /// Record `state`, returning whether it had already been visited.
+  fn visit(&mut self, state: &S) -> bool;
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> for HashSet<S> {
+  fn visit(&mut self, state: &S) -> bool {
+    !self.insert(state.clone())
+  }
+}
+
+/// A state that moves around a grid with a heading, so visits can be kept in
+/// a `Grid<DirSet<_>>` instead of a hash set.
+pub trait Walker {
+  type Pos: GridIndex;
+  type Dir: Direction;
+
+  fn pos(&self) -> Self::Pos;
+  fn dir(&self) -> Self::Dir;
+}
+
+impl<S: Walker> Visited<S> for Grid<DirSet<S::Dir>> {
+  fn visit(&mut self, state: &S) -> bool {
+    !self[state.pos()].insert(state.dir())
+  }
+}
+
+/// Run `step` from `start` until it exits or revisits a state.
+pub fn simulate<S, E>(
+  start: S,
+  visited: &mut impl Visited<S>,
+  mut step: impl FnMut(&S) -> Step<S, E>,
+) -> Outcome<S, E> {
+  let mut state = start;
+  visited.visit(&state);
+  loop {
+    match step(&state) {
+      Step::Exit(exit) => return Outcome::Exit(exit),
+      Step::Continue(next) if visited.visit(&next) => return Outcome::Loop(next),
+      Step::Continue(next) => state = next,
+    }
+  }
+}
+
+/// A simulation run along with every state it passed through.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trace<S, E> {
+  /// The states in order, starting with the initial state. On a loop, the
+  /// repeated state is not included a second time.
+  pub path: Vec<S>,
+  pub outcome: Outcome<S, E>,
+}
+
+impl<S: PartialEq, E> Trace<S, E> {
+  /// The index in `path` where the loop starts, if the simulation looped.
+  pub fn loop_start(&self) -> Option<usize> {
+    match &self.outcome {
+      Outcome::Loop(entry) => self.path.iter().position(|state| state == entry),
+      Outcome::Exit(_) => None,
+    }
+  }
+}
+
+/// Like [`simulate`], but recording the path taken.
+pub fn trace<S: Clone, E>(
+  start: S,
+  visited: &mut impl Visited<S>,
+  mut step: impl FnMut(&S) -> Step<S, E>,
+) -> Trace<S, E> {
+  let mut path = Vec::new();
+  let outcome = simulate(start, visited, |state| {
+    path.push(state.clone());
+    step(state)
+  });
+  Trace { path, outcome }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{ivec2, IVec2};
+
+  use super::*;
+  use crate::dir::Dir4;
+
+  /// Walks `n -> n * 2 % m`, exiting on zero.
+  fn doubling(m: u32) -> impl FnMut(&u32) -> Step<u32, &'static str> {
+    move |n| match n * 2 % m {
+      0 => Step::Exit("zero"),
+      next => Step::Continue(next),
+    }
+  }
+
+  #[test]
+  fn test_exit() {
+    let trace = trace(1, &mut HashSet::new(), doubling(8));
+    assert_eq!(vec![1, 2, 4], trace.path);
+    assert_eq!(Outcome::Exit("zero"), trace.outcome);
+    assert_eq!(None, trace.loop_start());
+  }
+
+  #[test]
+  fn test_loop() {
+    // 3 -> 6 -> 2 -> 4 -> 8 -> 6
+    let trace = trace(3, &mut HashSet::new(), doubling(10));
+    assert_eq!(vec![3, 6, 2, 4, 8], trace.path);
+    assert_eq!(Outcome::Loop(6), trace.outcome);
+    assert_eq!(Some(1), trace.loop_start());
+  }
+
+  #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+  struct Bot {
+    pos: IVec2,
+    dir: Dir4,
+  }
+
+  impl Walker for Bot {
+    type Pos = IVec2;
+    type Dir = Dir4;
+
+    fn pos(&self) -> IVec2 {
+      self.pos
+    }
+
+    fn dir(&self) -> Dir4 {
+      self.dir
+    }
+  }
+
+  #[test]
+  fn test_grid_visited() {
+    // Turns right at the edges of a 2x2 grid, so circles forever.
+    let mut visited = Grid::new(2, 2, DirSet::new());
+    let start = Bot {
+      pos: ivec2(0, 0),
+      dir: Dir4::East,
+    };
+    let outcome = simulate(start, &mut visited, |bot| {
+      let ahead = bot.pos + bot.dir.ivec2();
+      Step::<_, ()>::Continue(if in_bounds(ahead) {
+        Bot { pos: ahead, ..*bot }
+      } else {
+        Bot {
+          dir: bot.dir.turn_right(),
+          ..*bot
+        }
+      })
+    });
+    assert_eq!(Outcome::Loop(start), outcome);
+    assert!(visited.iter().all(|(_, dirs)| dirs.len() == 2));
+  }
+
+  fn in_bounds(pos: IVec2) -> bool {
+    (0..2).contains(&pos.x) && (0..2).contains(&pos.y)
+  }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+
+use crate::input::normalize_input;
+use crate::Answer;
+
+/// A day's puzzle: a parse step shared by both parts, and a solver for each.
+pub trait Solution {
+  /// The crate name of the day, e.g. `"day-01"`.
+  const DAY: &'static str;
+
+  type Parsed;
+
+  fn parse(input: &str) -> anyhow::Result<Self::Parsed>;
+  fn part1(parsed: &Self::Parsed) -> anyhow::Result<Answer>;
+  fn part2(parsed: &Self::Parsed) -> anyhow::Result<Answer>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+  One,
+  Two,
+}
+
+impl Part {
+  pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Part::One => write!(f, "part1"),
+      Part::Two => write!(f, "part2"),
+    }
+  }
+}
+
+impl FromStr for Part {
+  type Err = anyhow::Error;
+
+  fn from_str(s: &str) -> anyhow::Result<Self> {
+    match s {
+      "1" | "part1" => Ok(Part::One),
+      "2" | "part2" => Ok(Part::Two),
+      _ => Err(anyhow!("unknown part {:?}, expected part1 or part2", s)),
+    }
+  }
+}
+
+/// The outcome of running one part of a day.
+#[derive(Clone, Debug)]
+pub struct PartRun {
+  pub part: Part,
+  pub answer: Answer,
+  pub elapsed: Duration,
+}
+
+/// The outcome of running a day: the parse step, then each requested part.
+#[derive(Clone, Debug)]
+pub struct Run {
+  pub parse: Duration,
+  pub parts: Vec<PartRun>,
+}
+
+/// A type-erased [`Solution`], so a runner can hold every day in one table.
+#[derive(Clone, Copy)]
+pub struct Day {
+  pub name: &'static str,
+  run: fn(&str, &[Part]) -> anyhow::Result<Run>,
+}
+
+impl Day {
+  pub const fn of<S: Solution>() -> Day {
+    Day {
+      name: S::DAY,
+      run: run::<S>,
+    }
+  }
+
+  /// Parse `input`, tidied up with [`normalize_input`], and solve `parts`.
+  pub fn run(&self, input: &str, parts: &[Part]) -> anyhow::Result<Run> {
+    (self.run)(input, parts).with_context(|| format!("run {}", self.name))
+  }
+
+  /// Whether `name` refers to this day, either by crate name or by number.
+  pub fn matches(&self, name: &str) -> bool {
+    self.name == name
+      || self
+        .name
+        .strip_prefix("day-")
+        .zip(name.parse::<u32>().ok())
+        .is_some_and(|(n, day)| n.parse() == Ok(day))
+  }
+}
+
+impl std::fmt::Debug for Day {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Day").field("name", &self.name).finish()
+  }
+}
+
+fn run<S: Solution>(input: &str, parts: &[Part]) -> anyhow::Result<Run> {
+  let input = normalize_input(input);
+  let start = Instant::now();
+  let parsed = S::parse(&input).context("parse")?;
+  let parse = start.elapsed();
+
+  let parts = parts
+    .iter()
+    .map(|&part| {
+      let start = Instant::now();
+      let answer = match part {
+        Part::One => S::part1(&parsed),
+        Part::Two => S::part2(&parsed),
+      }
+      .with_context(|| part.to_string())?;
+      Ok(PartRun {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+      })
+    })
+    .collect::<anyhow::Result<_>>()?;
+
+  Ok(Run { parse, parts })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+  use rstest::rstest;
+
+  use super::*;
+
+  /// A toy solution for testing the machinery around [`Solution`]: part 1
+  /// sums the numbers and part 2 echoes them back as a list.
+  pub(crate) struct Sum;
+
+  impl Solution for Sum {
+    const DAY: &'static str = "day-07";
+
+    type Parsed = Vec<i64>;
+
+    fn parse(input: &str) -> anyhow::Result<Vec<i64>> {
+      Ok(
+        input
+          .split_whitespace()
+          .map(str::parse)
+          .collect::<Result<_, _>>()?,
+      )
+    }
+
+    fn part1(parsed: &Vec<i64>) -> anyhow::Result<Answer> {
+      Ok(parsed.iter().sum::<i64>().into())
+    }
+
+    fn part2(parsed: &Vec<i64>) -> anyhow::Result<Answer> {
+      Ok(format!("{:?}", parsed).into())
+    }
+  }
+
+  #[test]
+  fn test_run() -> anyhow::Result<()> {
+    let run = Day::of::<Sum>().run("1 2 3", &Part::BOTH)?;
+    assert_eq!(6, run.parts[0].answer);
+    assert_eq!("[1, 2, 3]", run.parts[1].answer);
+    Ok(())
+  }
+
+  #[test]
+  fn test_run_reports_parse_errors() {
+    let err = Day::of::<Sum>().run("1 x", &[Part::One]).unwrap_err();
+    assert_eq!(
+      "run day-07: parse: invalid digit found in string",
+      format!("{:#}", err)
+    );
+  }
+
+  #[rstest]
+  #[case("day-07", true)]
+  #[case("7", true)]
+  #[case("07", true)]
+  #[case("day-7", false)]
+  #[case("8", false)]
+  fn test_matches(#[case] name: &str, #[case] expected: bool) {
+    assert_eq!(expected, Day::of::<Sum>().matches(name));
+  }
+}
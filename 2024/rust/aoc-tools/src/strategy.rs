@@ -0,0 +1,150 @@
+//! proptest strategies for puzzle-shaped inputs, for checking properties of a
+//! day's solution on more than the examples. Each generates the input text
+//! the way it would appear in a puzzle input file.
+
+use std::ops::RangeInclusive;
+
+use proptest::collection::{vec, SizeRange};
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+/// Lines holding two whitespace-separated integers, e.g. `3   4`.
+pub fn column_pairs(
+  rows: impl Into<SizeRange>,
+  values: RangeInclusive<i64>,
+) -> impl Strategy<Value = String> {
+  vec((values.clone(), values), rows).prop_map(|pairs| {
+    pairs
+      .into_iter()
+      .map(|(a, b)| format!("{}   {}\n", a, b))
+      .collect()
+  })
+}
+
+/// Lines of space-separated levels. Neighbouring levels differ by at most
+/// four, so reports land near the safe/unsafe boundary.
+pub fn reports(
+  count: impl Into<SizeRange>,
+  levels: RangeInclusive<usize>,
+) -> impl Strategy<Value = String> {
+  let steps = (*levels.start()).max(1) - 1..=*levels.end() - 1;
+  let report = (10..=90i32, vec(-4..=4i32, steps)).prop_map(|(first, steps)| {
+    let levels = std::iter::once(first).chain(steps.into_iter().scan(first, |level, step| {
+      *level += step;
+      Some(*level)
+    }));
+    levels
+      .map(|level| level.to_string())
+      .collect::<Vec<_>>()
+      .join(" ")
+  });
+  vec(report, count).prop_map(|reports| reports.into_iter().map(|r| r + "\n").collect())
+}
+
+/// A rectangular grid of cells drawn from `alphabet`, one row per line.
+/// Repeat a cell in `alphabet` to make it more common.
+pub fn grid(
+  alphabet: &'static [u8],
+  width: RangeInclusive<usize>,
+  height: RangeInclusive<usize>,
+) -> impl Strategy<Value = String> {
+  (width, height)
+    .prop_flat_map(move |(width, height)| vec(vec(select(alphabet), width), height))
+    .prop_map(|rows| {
+      rows
+        .into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+    })
+}
+
+/// `a|b` ordering rules between two-digit pages, a blank line, then
+/// comma-separated updates of an odd number of distinct pages.
+///
+/// The pages follow a hidden total order with a rule for every pair, as in
+/// the puzzle, so every update has exactly one correct order.
+pub fn page_rules(
+  pages: RangeInclusive<usize>,
+  updates: impl Into<SizeRange>,
+) -> impl Strategy<Value = String> {
+  let updates = updates.into();
+  subsequence((10..100u8).collect::<Vec<_>>(), pages)
+    .prop_shuffle()
+    .prop_flat_map(move |order| {
+      let rules = order
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &a)| order[i + 1..].iter().map(move |&b| (a, b)))
+        .collect::<Vec<_>>();
+      let longest = (order.len() - 1) / 2;
+      let update = (0..=longest)
+        .prop_flat_map(move |half| subsequence(order.clone(), 2 * half + 1).prop_shuffle());
+      (Just(rules).prop_shuffle(), vec(update, updates.clone()))
+    })
+    .prop_map(|(rules, updates)| {
+      let rules = rules
+        .into_iter()
+        .map(|(a, b)| format!("{}|{}\n", a, b))
+        .collect::<String>();
+      let updates = updates
+        .into_iter()
+        .map(|update| {
+          update
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+            + "\n"
+        })
+        .collect::<String>();
+      format!("{}\n{}", rules, updates)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  proptest! {
+    #[test]
+    fn test_column_pairs(input in column_pairs(1..20, -5..=5)) {
+      for line in input.lines() {
+        prop_assert_eq!(2, line.split_whitespace().count());
+      }
+    }
+
+    #[test]
+    fn test_reports(input in reports(1..20, 1..=8)) {
+      for line in input.lines() {
+        let levels = line
+          .split(' ')
+          .map(|level| level.parse::<i32>().unwrap())
+          .collect::<Vec<_>>();
+        prop_assert!((1..=8).contains(&levels.len()));
+        prop_assert!(levels.windows(2).all(|w| (w[0] - w[1]).abs() <= 4));
+      }
+    }
+
+    #[test]
+    fn test_grid(input in grid(b"XMAS", 1..=10, 1..=10)) {
+      let grid = crate::grid::parse(&input, Ok).unwrap();
+      prop_assert!(grid.cells().iter().all(|cell| b"XMAS".contains(cell)));
+    }
+
+    #[test]
+    fn test_page_rules(input in page_rules(2..=20, 1..10)) {
+      let (rules, updates) = input.split_once("\n\n").unwrap();
+      let pages = rules
+        .lines()
+        .flat_map(|rule| rule.split('|'))
+        .collect::<std::collections::HashSet<_>>();
+      let n = pages.len();
+      prop_assert_eq!(n * (n - 1) / 2, rules.lines().count());
+      for update in updates.lines() {
+        let update = update.split(',').collect::<Vec<_>>();
+        prop_assert_eq!(1, update.len() % 2);
+        prop_assert!(update.iter().all(|page| pages.contains(page)));
+      }
+    }
+  }
+}
@@ -0,0 +1,161 @@
+//! Several implementations of the same part kept side by side, e.g. a plain
+//! `reference` and an optimized `fast`, so a rewrite can be checked against
+//! the version it replaces and benched next to it.
+
+use std::fmt::Display;
+
+use anyhow::{ensure, Context};
+
+use crate::input::normalize_input;
+use crate::{Answer, Part, Solution};
+
+/// One named implementation of a part.
+pub struct Variant<P: 'static> {
+  pub name: &'static str,
+  pub solve: fn(&P) -> anyhow::Result<Answer>,
+}
+
+impl<P> Variant<P> {
+  pub const fn new(name: &'static str, solve: fn(&P) -> anyhow::Result<Answer>) -> Self {
+    Variant { name, solve }
+  }
+}
+
+impl<P> Clone for Variant<P> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<P> Copy for Variant<P> {}
+
+impl<P> Display for Variant<P> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.name)
+  }
+}
+
+impl<P> std::fmt::Debug for Variant<P> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Variant").field("name", &self.name).finish()
+  }
+}
+
+/// A [`Solution`] with more than one implementation of a part.
+pub trait Variants: Solution<Parsed: 'static> {
+  /// Every implementation of `part`. The first is the reference the others
+  /// are checked against.
+  fn variants(part: Part) -> Vec<Variant<Self::Parsed>>;
+}
+
+/// Run every variant of `part` on `parsed`, failing unless they all give the
+/// reference's answer.
+pub fn compare<S: Variants>(parsed: &S::Parsed, part: Part) -> anyhow::Result<Option<Answer>> {
+  let mut variants = S::variants(part).into_iter();
+  let Some(reference) = variants.next() else {
+    return Ok(None);
+  };
+  let expected = (reference.solve)(parsed).with_context(|| format!("{} {}", part, reference))?;
+  for variant in variants {
+    let answer = (variant.solve)(parsed).with_context(|| format!("{} {}", part, variant))?;
+    ensure!(
+      answer == expected,
+      "{} {}: {} gave {}, but {} gave {}",
+      S::DAY,
+      part,
+      variant,
+      answer,
+      reference,
+      expected
+    );
+  }
+  Ok(Some(expected))
+}
+
+/// Parse `input` and [`compare`] the variants of both parts.
+pub fn check<S: Variants>(input: &str) -> anyhow::Result<()> {
+  let parsed = S::parse(&normalize_input(input)).context("parse")?;
+  for part in Part::BOTH {
+    compare::<S>(&parsed, part)?;
+  }
+  Ok(())
+}
+
+/// Generate tests checking a day's [`Variants`] against each other on every
+/// example file, on the real input, and on inputs drawn from an optional
+/// proptest strategy. The real-input test is ignored by default, since the
+/// input may not be there; run it with `--ignored`.
+///
+/// Uses the same `examples/` files and build script as
+/// [`example_tests!`](crate::example_tests); the strategy needs `proptest` as
+/// a dev-dependency.
+#[macro_export]
+macro_rules! variant_tests {
+  ($solution:ty) => {
+    #[::rstest::rstest]
+    fn variants_on_examples(
+      #[files("examples/${CARGO_PKG_NAME}-*.txt")] path: ::std::path::PathBuf,
+    ) -> ::anyhow::Result<()> {
+      let example = $crate::example::Example::load(&path)?;
+      $crate::variant::check::<$solution>(&example.input)
+    }
+
+    #[test]
+    #[ignore = "needs the puzzle input; run with --ignored"]
+    fn variants_on_input() -> ::anyhow::Result<()> {
+      let inputs = $crate::input::Inputs::for_crate(env!("CARGO_MANIFEST_DIR"));
+      let input = inputs.read(<$solution as $crate::Solution>::DAY, None)?;
+      $crate::variant::check::<$solution>(&input)
+    }
+  };
+  ($solution:ty, $strategy:expr) => {
+    $crate::variant_tests!($solution);
+
+    ::proptest::proptest! {
+      #[test]
+      fn variants_on_generated(input in $strategy) {
+        $crate::variant::check::<$solution>(&input).unwrap();
+      }
+    }
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::solution::tests::Sum;
+
+  impl Variants for Sum {
+    fn variants(part: Part) -> Vec<Variant<Vec<i64>>> {
+      match part {
+        Part::One => vec![
+          Variant::new("reference", Sum::part1),
+          Variant::new("reversed", |parsed| {
+            Ok(parsed.iter().rev().sum::<i64>().into())
+          }),
+        ],
+        Part::Two => vec![
+          Variant::new("reference", Sum::part2),
+          Variant::new("sorted", |parsed| {
+            let mut sorted = parsed.clone();
+            sorted.sort();
+            Ok(format!("{:?}", sorted).into())
+          }),
+        ],
+      }
+    }
+  }
+
+  #[test]
+  fn test_compare() -> anyhow::Result<()> {
+    let parsed = vec![1, 5, 3];
+    assert_eq!(Some(Answer::from(9)), compare::<Sum>(&parsed, Part::One)?);
+    assert_eq!(
+      "day-07 part2: sorted gave [1, 3, 5], but reference gave [1, 5, 3]",
+      compare::<Sum>(&parsed, Part::Two).unwrap_err().to_string()
+    );
+    check::<Sum>("1 3 5")?;
+    assert!(check::<Sum>("1 5 3").is_err());
+    Ok(())
+  }
+}
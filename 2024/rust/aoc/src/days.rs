@@ -0,0 +1,22 @@
+use aoc_tools::Day;
+
+// New days need an entry here (and a dependency in Cargo.toml) after
+// `just generate`.
+pub static DAYS: &[Day] = &[
+  Day::of::<day_01::Day01>(),
+  Day::of::<day_02::Day02>(),
+  Day::of::<day_03::Day03>(),
+  Day::of::<day_04::Day04>(),
+  Day::of::<day_05::Day05>(),
+  Day::of::<day_06::Day06>(),
+];
+
+pub fn find(name: &str) -> anyhow::Result<&'static Day> {
+  DAYS.iter().find(|day| day.matches(name)).ok_or_else(|| {
+    anyhow::anyhow!(
+      "no day named {:?}; registered days are {}",
+      name,
+      DAYS.iter().map(|d| d.name).collect::<Vec<_>>().join(", ")
+    )
+  })
+}
@@ -0,0 +1,489 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{bail, Context};
+use aoc_tools::answers::{Answers, Verdict};
+use aoc_tools::bench::{self, History};
+use aoc_tools::fetch::{Fetched, Fetcher};
+use aoc_tools::input::Inputs;
+use aoc_tools::solution::Run;
+use aoc_tools::{Day, Part};
+use clap::{Parser, Subcommand};
+use serde_json::json;
+
+mod days;
+mod summary;
+
+const YEAR: u32 = 2024;
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+  /// Directory holding `<day>.txt` puzzle inputs [default: inputs/, or
+  /// $AOC_INPUT_DIR].
+  #[arg(long, global = true)]
+  input_dir: Option<PathBuf>,
+
+  /// The registry of accepted answers [default: answers.toml].
+  #[arg(long, global = true)]
+  answers: Option<PathBuf>,
+
+  /// The history of benchmark runs [default: bench-history.jsonl].
+  #[arg(long, global = true)]
+  history: Option<PathBuf>,
+
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+  /// Run a day against a puzzle input.
+  Run {
+    /// The day to run, e.g. `day-05` or `5`. With `--verify`, every day with
+    /// recorded answers is checked if omitted.
+    #[arg(required_unless_present = "verify")]
+    day: Option<String>,
+    /// The part to run (`part1` or `part2`); runs both if omitted.
+    part: Option<Part>,
+    /// Read the puzzle input from this file instead; `-` reads stdin.
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Check the answers of solved parts against the answer registry.
+    #[arg(long, conflicts_with = "input")]
+    verify: bool,
+    /// Record the answers as accepted in the answer registry. Only answers
+    /// for the real puzzle input are recorded, so not with `--input`.
+    #[arg(long, conflicts_with_all = ["verify", "input"])]
+    record: bool,
+    /// Print the run as a JSON object instead.
+    #[arg(long, conflicts_with = "verify")]
+    json: bool,
+  },
+  /// Download puzzle inputs into the input directory, skipping any already
+  /// there.
+  Fetch {
+    /// The days to fetch; fetches every registered day if omitted.
+    days: Vec<String>,
+    /// The adventofcode.com session cookie [default: $AOC_SESSION].
+    #[arg(long)]
+    session: Option<String>,
+    /// The server to fetch from [default: https://adventofcode.com, or
+    /// $AOC_BASE_URL].
+    #[arg(long)]
+    base_url: Option<String>,
+  },
+  /// Benchmark a day, record the results in the bench history, and compare
+  /// them with the previous run.
+  Bench {
+    /// The day to benchmark, e.g. `day-05` or `5`.
+    day: String,
+    /// Only run the benchmarks matching this divan filter, e.g. `part1`.
+    filter: Option<String>,
+    #[command(flatten)]
+    compare: CompareArgs,
+  },
+  /// Compare a day's latest recorded bench run with an earlier one.
+  Compare {
+    /// The day to compare, e.g. `day-05` or `5`.
+    day: String,
+    #[command(flatten)]
+    compare: CompareArgs,
+  },
+  /// Run every registered day and summarize the answers, whether they are
+  /// verified, and how long they took.
+  Summary {
+    /// Print the table as Markdown, e.g. for the README.
+    #[arg(long)]
+    markdown: bool,
+  },
+  /// List the registered days.
+  List,
+}
+
+#[derive(clap::Args)]
+struct CompareArgs {
+  /// Compare with the latest run at this git revision instead of the
+  /// previous run.
+  #[arg(long)]
+  baseline: Option<String>,
+  /// Flag benchmarks whose median slowed down by more than this percentage.
+  #[arg(long, default_value_t = 5.0)]
+  threshold: f64,
+}
+
+fn workspace() -> &'static Path {
+  let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+  manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+impl Cli {
+  fn inputs(&self) -> Inputs {
+    let inputs = Inputs::new(workspace());
+    match &self.input_dir {
+      Some(dir) => inputs.with_dir(dir),
+      None => inputs,
+    }
+  }
+
+  fn answers(&self) -> anyhow::Result<Answers> {
+    match &self.answers {
+      Some(path) => Answers::load(path),
+      None => Answers::load(workspace().join("answers.toml")),
+    }
+  }
+
+  fn history(&self) -> anyhow::Result<History> {
+    match &self.history {
+      Some(path) => History::load(path),
+      None => History::load(workspace().join("bench-history.jsonl")),
+    }
+  }
+}
+
+fn read_input(path: &Path) -> anyhow::Result<String> {
+  if path.as_os_str() == "-" {
+    let mut input = String::new();
+    std::io::stdin()
+      .read_to_string(&mut input)
+      .context("read input from stdin")?;
+    Ok(input)
+  } else {
+    std::fs::read_to_string(path).with_context(|| format!("read input from {}", path.display()))
+  }
+}
+
+fn print_run(day: &Day, run: &Run) {
+  println!("{} parse ({:?})", day.name, run.parse);
+  for part in &run.parts {
+    let sep = if part.answer.is_multiline() {
+      "\n"
+    } else {
+      " "
+    };
+    println!(
+      "{} {} ({:?}):{}{}",
+      day.name, part.part, part.elapsed, sep, part.answer
+    );
+  }
+}
+
+fn print_json(day: &Day, run: &Run) -> anyhow::Result<()> {
+  let parts = run
+    .parts
+    .iter()
+    .map(|part| {
+      json!({
+        "part": part.part.to_string(),
+        "answer": part.answer,
+        "elapsed_ns": part.elapsed.as_nanos() as u64,
+      })
+    })
+    .collect::<Vec<_>>();
+  let report = json!({
+    "day": day.name,
+    "parse_ns": run.parse.as_nanos() as u64,
+    "parts": parts,
+  });
+  println!("{}", serde_json::to_string(&report)?);
+  Ok(())
+}
+
+fn run(
+  inputs: &Inputs,
+  answers: Option<&mut Answers>,
+  day: &str,
+  part: Option<Part>,
+  input: Option<&Path>,
+  json: bool,
+) -> anyhow::Result<()> {
+  let day = days::find(day)?;
+  let input = match input {
+    Some(path) => read_input(path)?,
+    None => inputs.read(day.name, part)?,
+  };
+  let parts = match part {
+    Some(part) => vec![part],
+    None => Part::BOTH.to_vec(),
+  };
+
+  let run = day.run(&input, &parts)?;
+  if json {
+    print_json(day, &run)?;
+  } else {
+    print_run(day, &run);
+  }
+
+  if let Some(answers) = answers {
+    for part in run.parts {
+      answers.set(day.name, part.part, part.answer);
+    }
+    answers.save()?;
+    eprintln!("recorded answers in {}", answers.path().display());
+  }
+  Ok(())
+}
+
+fn verify(
+  inputs: &Inputs,
+  answers: &Answers,
+  day: Option<&str>,
+  part: Option<Part>,
+) -> anyhow::Result<()> {
+  let days = match day {
+    Some(day) => vec![days::find(day)?],
+    None => days::DAYS
+      .iter()
+      .filter(|day| answers.day(day.name).is_some())
+      .collect(),
+  };
+
+  let (mut checked, mut failed) = (0, 0);
+  for day in days {
+    let parts = answers
+      .day(day.name)
+      .map(|recorded| recorded.parts())
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|p| part.is_none_or(|part| part == *p))
+      .collect::<Vec<_>>();
+    if parts.is_empty() {
+      println!("{} has no recorded answers", day.name);
+      continue;
+    }
+    let run = inputs
+      .read(day.name, part)
+      .and_then(|input| day.run(&input, &parts));
+    let run = match run {
+      Ok(run) => run,
+      Err(e) => {
+        (checked, failed) = (checked + parts.len(), failed + parts.len());
+        println!("{} FAILED: {:#}", day.name, e);
+        continue;
+      }
+    };
+
+    for part in run.parts {
+      checked += 1;
+      match answers.check(day.name, part.part, &part.answer) {
+        Verdict::Match => println!("{} {} ok ({:?})", day.name, part.part, part.elapsed),
+        Verdict::Mismatch { expected } => {
+          failed += 1;
+          println!(
+            "{} {} MISMATCH: got {}, expected {}",
+            day.name, part.part, part.answer, expected
+          );
+        }
+        Verdict::Unknown => unreachable!("only parts with recorded answers are run"),
+      }
+    }
+  }
+
+  if failed > 0 {
+    bail!("{} of {} answers did not match", failed, checked);
+  }
+  println!("{} answers verified", checked);
+  Ok(())
+}
+
+fn fetch(
+  inputs: &Inputs,
+  days: &[String],
+  session: Option<&str>,
+  base_url: Option<&str>,
+) -> anyhow::Result<()> {
+  let fetcher = match session {
+    Some(session) => Fetcher::new(YEAR, session),
+    None => Fetcher::from_env(YEAR)?,
+  };
+  let fetcher = match base_url {
+    Some(url) => fetcher.with_base_url(url),
+    None => fetcher,
+  };
+
+  let days = if days.is_empty() {
+    days::DAYS.iter().collect::<Vec<_>>()
+  } else {
+    days
+      .iter()
+      .map(|day| days::find(day))
+      .collect::<anyhow::Result<_>>()?
+  };
+  for day in days {
+    match fetcher.fetch(inputs, day.name)? {
+      Fetched::Cached(path) => println!("{} cached at {}", day.name, path.display()),
+      Fetched::Downloaded(path) => println!("{} saved to {}", day.name, path.display()),
+    }
+  }
+  Ok(())
+}
+
+fn bench(
+  history: &mut History,
+  day: &str,
+  filter: Option<&str>,
+  args: &CompareArgs,
+) -> anyhow::Result<()> {
+  let day = days::find(day)?;
+  let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+  let output = std::process::Command::new(cargo)
+    .current_dir(workspace())
+    .args(["bench", "-p", day.name, "--bench"])
+    .arg(format!("{}-bench", day.name))
+    .args(filter)
+    .stderr(Stdio::inherit())
+    .output()
+    .context("run cargo bench")?;
+  let stdout = String::from_utf8_lossy(&output.stdout);
+  print!("{}", stdout);
+  if !output.status.success() {
+    bail!("cargo bench failed with {}", output.status);
+  }
+
+  let results = bench::parse_divan(&stdout)?;
+  let (rev, date) = (bench::git_rev(workspace()), bench::now());
+  history.append(
+    results
+      .iter()
+      .map(|result| result.row(day.name, &rev, &date))
+      .collect(),
+  )?;
+  eprintln!(
+    "recorded {} benchmarks in {}",
+    results.len(),
+    history.path().display()
+  );
+  compare(history, day.name, args)
+}
+
+fn compare(history: &History, day: &str, args: &CompareArgs) -> anyhow::Result<()> {
+  let day = days::find(day)?;
+  let runs = history.runs(day.name);
+  let Some((latest, earlier)) = runs.split_last() else {
+    bail!("no recorded bench runs for {}", day.name);
+  };
+  let baseline = match &args.baseline {
+    Some(rev) => earlier
+      .iter()
+      .rev()
+      .find(|run| run[0].rev.starts_with(rev.as_str()))
+      .with_context(|| format!("no recorded bench run of {} at {}", day.name, rev))?,
+    None => match earlier.last() {
+      Some(run) => run,
+      None => {
+        println!("{} has no earlier bench run to compare with", day.name);
+        return Ok(());
+      }
+    },
+  };
+
+  println!(
+    "{} at {} ({}) against {} ({})",
+    day.name, latest[0].rev, latest[0].date, baseline[0].rev, baseline[0].date
+  );
+  let changes = bench::compare(baseline, latest);
+  let width = changes.iter().map(|c| c.bench.len()).max().unwrap_or(0);
+  let mut regressions = 0;
+  for change in &changes {
+    let after = change.after.median();
+    match (change.before, change.change()) {
+      (Some(before), Some(relative)) => {
+        let flag = if change.is_regression(args.threshold / 100.0) {
+          regressions += 1;
+          "  REGRESSION"
+        } else {
+          ""
+        };
+        println!(
+          "  {:width$}  {:>10?} -> {:>10?}  {:+6.1}%{}",
+          change.bench,
+          before.median(),
+          after,
+          relative * 100.0,
+          flag
+        );
+      }
+      _ => println!(
+        "  {:width$}  {:>10} -> {:>10?}  new",
+        change.bench, "", after
+      ),
+    }
+  }
+
+  if regressions > 0 {
+    bail!(
+      "{} benchmarks slowed down by more than {}%",
+      regressions,
+      args.threshold
+    );
+  }
+  Ok(())
+}
+
+fn main() -> anyhow::Result<()> {
+  tracing_subscriber::fmt::init();
+
+  let cli = Cli::parse();
+  let inputs = cli.inputs();
+  match &cli.command {
+    Command::Run {
+      day,
+      part,
+      verify: true,
+      ..
+    } => verify(&inputs, &cli.answers()?, day.as_deref(), *part),
+    Command::Run {
+      day,
+      part,
+      input,
+      record,
+      json,
+      ..
+    } => {
+      let mut answers = if *record { Some(cli.answers()?) } else { None };
+      let day = day.as_deref().context("no day given")?;
+      run(
+        &inputs,
+        answers.as_mut(),
+        day,
+        *part,
+        input.as_deref(),
+        *json,
+      )
+    }
+    Command::Fetch {
+      days,
+      session,
+      base_url,
+    } => fetch(&inputs, days, session.as_deref(), base_url.as_deref()),
+    Command::Bench {
+      day,
+      filter,
+      compare: args,
+    } => bench(&mut cli.history()?, day, filter.as_deref(), args),
+    Command::Compare { day, compare: args } => compare(&cli.history()?, day, args),
+    Command::Summary { markdown } => {
+      summary::summary(&inputs, &cli.answers()?, *markdown);
+      Ok(())
+    }
+    Command::List => {
+      for day in days::DAYS {
+        println!("{}", day.name);
+      }
+      Ok(())
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_record_conflicts_with_input() {
+    assert!(Cli::try_parse_from(["aoc", "run", "5", "--record"]).is_ok());
+    assert!(Cli::try_parse_from(["aoc", "run", "5", "-i", "example.txt", "--record"]).is_err());
+    assert!(Cli::try_parse_from(["aoc", "run", "5", "-i", "-", "--record"]).is_err());
+  }
+}
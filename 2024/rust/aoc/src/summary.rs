@@ -0,0 +1,254 @@
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+
+use aoc_tools::answers::{Answers, Verdict};
+use aoc_tools::input::Inputs;
+use aoc_tools::{Answer, Day, Part};
+
+use crate::days;
+
+const HEADER: [&str; 5] = ["day", "part", "answer", "status", "time"];
+
+/// One line of the summary table.
+struct Row([String; 5]);
+
+impl Row {
+  fn new(day: &str, part: &str, answer: &str, status: &str, time: Option<Duration>) -> Row {
+    Row([
+      day.to_owned(),
+      part.to_owned(),
+      answer.to_owned(),
+      status.to_owned(),
+      time.map_or(String::new(), |time| format!("{:?}", time)),
+    ])
+  }
+}
+
+/// Run both parts of every registered day and print a table of the answers,
+/// whether they match the answer registry, and how long they took.
+///
+/// Errors only get their first line in the table, and are printed in full
+/// to stderr after it.
+pub fn summary(inputs: &Inputs, answers: &Answers, markdown: bool) {
+  let mut rows = Vec::new();
+  let mut errors = Vec::new();
+  let mut total = Duration::ZERO;
+  for day in days::DAYS {
+    total += summarize(day, inputs, answers, &mut rows, &mut errors);
+  }
+  rows.push(Row::new("total", "", "", "", Some(total)));
+
+  if markdown {
+    print!("{}", render_markdown(&rows));
+  } else {
+    print!("{}", render_plain(&rows));
+  }
+  for e in errors {
+    eprintln!("\n{:#}", e);
+  }
+}
+
+/// Add the rows for one day, returning the time it took.
+fn summarize(
+  day: &Day,
+  inputs: &Inputs,
+  answers: &Answers,
+  rows: &mut Vec<Row>,
+  errors: &mut Vec<anyhow::Error>,
+) -> Duration {
+  // Find each part's input as `aoc run <day> <part>` does, so a day with
+  // separate input1.txt and input2.txt files runs each part on its own. A
+  // part falls back to the other part's file, so both have an input or
+  // neither does.
+  let (Ok(path1), Ok(path2)) = (
+    inputs.locate(day.name, Some(Part::One)),
+    inputs.locate(day.name, Some(Part::Two)),
+  ) else {
+    rows.push(Row::new(day.name, "", "", "no input", None));
+    return Duration::ZERO;
+  };
+  let runs = if path1 == path2 {
+    vec![(path1, Part::BOTH.to_vec())]
+  } else {
+    vec![(path1, vec![Part::One]), (path2, vec![Part::Two])]
+  };
+  runs
+    .iter()
+    .map(|(path, parts)| summarize_run(day, path, parts, answers, rows, errors))
+    .sum()
+}
+
+/// Add the rows for running `parts` of a day on the input at `path`,
+/// returning the time it took.
+fn summarize_run(
+  day: &Day,
+  path: &Path,
+  parts: &[Part],
+  answers: &Answers,
+  rows: &mut Vec<Row>,
+  errors: &mut Vec<anyhow::Error>,
+) -> Duration {
+  let run = std::fs::read_to_string(path)
+    .with_context(|| format!("read {}", path.display()))
+    .and_then(|input| day.run(&input, parts));
+  let run = match run {
+    Ok(run) => run,
+    Err(e) => {
+      rows.push(error_row(day.name, &e));
+      errors.push(e);
+      return Duration::ZERO;
+    }
+  };
+
+  rows.push(Row::new(day.name, "parse", "", "", Some(run.parse)));
+  for part in &run.parts {
+    let status = match answers.check(day.name, part.part, &part.answer) {
+      Verdict::Match => "verified".to_owned(),
+      Verdict::Mismatch { expected } => format!("MISMATCH (expected {})", one_line(&expected)),
+      Verdict::Unknown => "unverified".to_owned(),
+    };
+    rows.push(Row::new(
+      day.name,
+      &part.part.to_string(),
+      &one_line(&part.answer),
+      &status,
+      Some(part.elapsed),
+    ));
+  }
+  run.parse + run.parts.iter().map(|part| part.elapsed).sum::<Duration>()
+}
+
+/// A row for a day that failed to run. Errors such as a [`ParseError`]'s
+/// snippet span several lines, so only the first goes in the table.
+///
+/// [`ParseError`]: aoc_tools::ParseError
+fn error_row(day: &str, e: &anyhow::Error) -> Row {
+  let message = format!("{:#}", e);
+  let first = message.lines().next().unwrap_or_default();
+  Row::new(day, "", "", &format!("error: {}", first), None)
+}
+
+/// Multi-line answers don't fit in a table cell.
+fn one_line(answer: &Answer) -> String {
+  if answer.is_multiline() {
+    format!("({} lines)", answer.to_string().lines().count())
+  } else {
+    answer.to_string()
+  }
+}
+
+fn render_plain(rows: &[Row]) -> String {
+  let widths = (0..HEADER.len())
+    .map(|i| {
+      rows
+        .iter()
+        .map(|row| row.0[i].chars().count())
+        .chain([HEADER[i].len()])
+        .max()
+        .unwrap_or(0)
+    })
+    .collect::<Vec<_>>();
+  let line = |cells: &[&str]| {
+    let cells = cells
+      .iter()
+      .zip(&widths)
+      .map(|(cell, width)| format!("{:width$}", cell, width = width))
+      .collect::<Vec<_>>();
+    format!("{}\n", cells.join("  ").trim_end())
+  };
+
+  let mut out = line(&HEADER);
+  for row in rows {
+    out += &line(&row.0.each_ref().map(String::as_str));
+  }
+  out
+}
+
+fn render_markdown(rows: &[Row]) -> String {
+  let line = |cells: &[&str]| format!("| {} |\n", cells.join(" | "));
+  let mut out = line(&HEADER);
+  out += &line(&["---", "---", "---:", "---", "---:"]);
+  for row in rows {
+    let cells = row.0.each_ref().map(|cell| cell.replace('|', "\\|"));
+    out += &line(&cells.each_ref().map(String::as_str));
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rows() -> Vec<Row> {
+    vec![
+      Row::new(
+        "day-01",
+        "part1",
+        "11",
+        "verified",
+        Some(Duration::from_micros(5)),
+      ),
+      Row::new("day-02", "", "", "no input", None),
+    ]
+  }
+
+  #[test]
+  fn test_render_plain() {
+    assert_eq!(
+      "day     part   answer  status    time
+day-01  part1  11      verified  5µs
+day-02                 no input
+",
+      render_plain(&rows())
+    );
+  }
+
+  #[test]
+  fn test_render_markdown() {
+    assert_eq!(
+      "| day | part | answer | status | time |
+| --- | --- | ---: | --- | ---: |
+| day-01 | part1 | 11 | verified | 5µs |
+| day-02 |  |  | no input |  |
+",
+      render_markdown(&rows())
+    );
+  }
+
+  #[test]
+  fn test_multiline_error() {
+    let e = anyhow::Error::from(aoc_tools::ParseError::at("1 x\n", 2, "bad number"));
+    let rows = vec![error_row("day-03", &e.context("parse"))];
+    assert_eq!("error: parse: line 1, column 3: bad number", rows[0].0[3]);
+    assert_eq!(3, render_markdown(&rows).lines().count());
+    assert_eq!(2, render_plain(&rows).lines().count());
+  }
+
+  #[test]
+  fn test_per_part_inputs() -> anyhow::Result<()> {
+    let root = tempfile::tempdir()?;
+    std::fs::create_dir(root.path().join("day-01"))?;
+    std::fs::write(root.path().join("day-01/input1.txt"), "3   4\n")?;
+    std::fs::write(root.path().join("day-01/input2.txt"), "2   2\n2   2\n")?;
+    let inputs = Inputs::new(root.path()).with_dir(root.path().join("inputs"));
+    let answers = Answers::load(root.path().join("answers.toml"))?;
+
+    let mut rows = Vec::new();
+    summarize(
+      days::find("day-01")?,
+      &inputs,
+      &answers,
+      &mut rows,
+      &mut Vec::new(),
+    );
+    let answers = rows
+      .iter()
+      .filter(|row| row.0[1].starts_with("part"))
+      .map(|row| (row.0[1].as_str(), row.0[2].as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(vec![("part1", "1"), ("part2", "8")], answers);
+    Ok(())
+  }
+}
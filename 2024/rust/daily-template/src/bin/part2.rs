@@ -1,12 +0,0 @@
-use {{crate_name}}::part2::process;
-use anyhow::Context;
-
-#[tracing::instrument]
-fn main() -> anyhow::Result<()> {
-    tracing_subscriber::fmt::init();
-
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
-    println!("{}", result);
-    Ok(())
-}
@@ -1,2 +1,31 @@
+use aoc_tools::Answer;
+use model::Puzzle;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct {{project-name | upper_camel_case}};
+
+impl aoc_tools::Solution for {{project-name | upper_camel_case}} {
+  const DAY: &'static str = "{{project-name}}";
+
+  type Parsed = Puzzle;
+
+  fn parse(input: &str) -> anyhow::Result<Puzzle> {
+    Puzzle::parse(input)
+  }
+
+  fn part1(puzzle: &Puzzle) -> anyhow::Result<Answer> {
+    part1::solve(puzzle)
+  }
+
+  fn part2(puzzle: &Puzzle) -> anyhow::Result<Answer> {
+    part2::solve(puzzle)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::{{project-name | upper_camel_case}});
+}
@@ -0,0 +1,9 @@
+/// The puzzle input, parsed once and shared by both parts.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Puzzle {}
+
+impl Puzzle {
+  pub fn parse(_input: &str) -> anyhow::Result<Puzzle> {
+    todo!("parse the input");
+  }
+}
@@ -1,17 +1,12 @@
-#[tracing::instrument]
-pub fn process(_input: &str) -> anyhow::Result<String> {
+use aoc_tools::Answer;
+
+use crate::model::Puzzle;
+
+pub fn solve(_puzzle: &Puzzle) -> anyhow::Result<Answer> {
   todo!("day 01 - part 1");
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  fn test_process() -> anyhow::Result<()> {
-    todo!("haven't built test yet");
-    let input = "";
-    assert_eq!("", process(input)?);
-    Ok(())
-  }
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&Puzzle::parse(input)?)
 }
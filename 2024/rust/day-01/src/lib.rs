@@ -1,2 +1,31 @@
+use aoc_tools::Answer;
+use model::Lists;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day01;
+
+impl aoc_tools::Solution for Day01 {
+  const DAY: &'static str = "day-01";
+
+  type Parsed = Lists;
+
+  fn parse(input: &str) -> anyhow::Result<Lists> {
+    Lists::parse(input)
+  }
+
+  fn part1(lists: &Lists) -> anyhow::Result<Answer> {
+    part1::solve(lists)
+  }
+
+  fn part2(lists: &Lists) -> anyhow::Result<Answer> {
+    part2::solve(lists)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::Day01);
+}
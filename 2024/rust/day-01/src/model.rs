@@ -0,0 +1,56 @@
+use aoc_tools::error::{parse_at, ParseError};
+
+/// The two columns of location IDs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lists {
+  pub left: Vec<i64>,
+  pub right: Vec<i64>,
+}
+
+impl Lists {
+  /// Parse each non-blank line of `input` as a pair of location IDs.
+  pub fn parse(input: &str) -> anyhow::Result<Lists> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+      let fields = line.split_whitespace().collect::<Vec<_>>();
+      let [x, y] = fields[..] else {
+        let at = fields.get(2).copied().unwrap_or(&line[line.len()..]);
+        let message = format!("expected two numbers, found {}", fields.len());
+        return Err(ParseError::at_slice(input, at, message).into());
+      };
+      left.push(parse_at(input, x)?);
+      right.push(parse_at(input, y)?);
+    }
+    Ok(Lists { left, right })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rstest::rstest;
+
+  #[test]
+  fn test_parse() -> anyhow::Result<()> {
+    let lists = Lists::parse("\n  3   4\n  4   3\n")?;
+    assert_eq!(vec![3, 4], lists.left);
+    assert_eq!(vec![4, 3], lists.right);
+    Ok(())
+  }
+
+  #[rstest]
+  #[case("3   4\n4   x\n", (2, 5), "invalid digit found in string")]
+  #[case("3   4\n4\n", (2, 2), "expected two numbers, found 1")]
+  #[case("3   4\n4   3   5\n", (2, 9), "expected two numbers, found 3")]
+  fn test_parse_errors(
+    #[case] input: &str,
+    #[case] position: (usize, usize),
+    #[case] message: &str,
+  ) {
+    let err = Lists::parse(input).unwrap_err();
+    let err = err.downcast_ref::<ParseError>().unwrap();
+    assert_eq!(position, (err.line, err.column));
+    assert_eq!(message, err.message);
+  }
+}
@@ -1,45 +1,43 @@
-use itertools::Itertools;
+use aoc_tools::Answer;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> anyhow::Result<String> {
-  Ok({
-    let mut xs = Vec::new();
-    let mut ys = Vec::new();
-    for (x, y) in input
-      .lines()
-      .filter_map(|line| line.split_whitespace().next_tuple())
-    {
-      xs.push(x);
-      ys.push(y);
-    }
-    xs.sort();
-    ys.sort();
+use crate::model::Lists;
+
+pub fn solve(lists: &Lists) -> anyhow::Result<Answer> {
+  let mut xs = lists.left.clone();
+  let mut ys = lists.right.clone();
+  xs.sort();
+  ys.sort();
+  Ok(
     xs.iter()
       .zip(ys.iter())
-      .map(|(x, y)| {
-        let (x, y): (i64, i64) = (x.parse().unwrap(), y.parse().unwrap());
-        (x - y).abs()
-      })
+      .map(|(x, y)| (x - y).abs())
       .sum::<i64>()
-      .to_string()
-  })
+      .into(),
+  )
+}
+
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&Lists::parse(input)?)
 }
 
 #[cfg(test)]
 mod tests {
+  use aoc_tools::strategy::column_pairs;
+  use proptest::prelude::*;
+
   use super::*;
 
-  #[test]
-  fn test_process() -> anyhow::Result<()> {
-    let input = r#"
-      3   4
-      4   3
-      2   5
-      1   3
-      3   9
-      3   3
-    "#;
-    assert_eq!("11", process(input)?);
-    Ok(())
+  proptest! {
+    /// The total distance doesn't depend on which list is which.
+    #[test]
+    fn test_swap_lists(input in column_pairs(0..50, 0..=99)) {
+      let lists = Lists::parse(&input).unwrap();
+      let swapped = Lists {
+        left: lists.right.clone(),
+        right: lists.left.clone(),
+      };
+      prop_assert_eq!(solve(&lists).unwrap(), solve(&swapped).unwrap());
+    }
   }
 }
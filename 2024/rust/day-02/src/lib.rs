@@ -1,2 +1,31 @@
+use aoc_tools::Answer;
+use model::Reports;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day02;
+
+impl aoc_tools::Solution for Day02 {
+  const DAY: &'static str = "day-02";
+
+  type Parsed = Reports;
+
+  fn parse(input: &str) -> anyhow::Result<Reports> {
+    Reports::parse(input)
+  }
+
+  fn part1(reports: &Reports) -> anyhow::Result<Answer> {
+    part1::solve(reports)
+  }
+
+  fn part2(reports: &Reports) -> anyhow::Result<Answer> {
+    part2::solve(reports)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::Day02);
+}
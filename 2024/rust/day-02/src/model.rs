@@ -0,0 +1,20 @@
+use aoc_tools::error::parse_at;
+
+/// One report per non-blank line, each a list of levels.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Reports(pub Vec<Vec<i32>>);
+
+impl Reports {
+  pub fn parse(input: &str) -> anyhow::Result<Reports> {
+    let reports = input
+      .lines()
+      .filter(|l| !l.trim().is_empty())
+      .map(|l| {
+        l.split_whitespace()
+          .map(|s| parse_at::<i32>(input, s))
+          .collect::<Result<Vec<i32>, _>>()
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    Ok(Reports(reports))
+  }
+}
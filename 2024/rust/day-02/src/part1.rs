@@ -1,17 +1,12 @@
-use itertools::Itertools;
-use tracing::info;
+use aoc_tools::Answer;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> anyhow::Result<String> {
+use crate::model::Reports;
+
+pub fn solve(reports: &Reports) -> anyhow::Result<Answer> {
   Ok(
-    input
-      .lines()
-      .map(|l| {
-        l.split_whitespace()
-          .map(|s| s.parse::<i32>().unwrap())
-          .collect::<Vec<i32>>()
-      })
-      .filter(|l| !l.is_empty())
+    reports
+      .0
+      .iter()
       .map(|levels| -> u32 {
         let mut last_diff = 0;
         for i in 1..levels.len() {
@@ -24,28 +19,26 @@ pub fn process(input: &str) -> anyhow::Result<String> {
           }
           last_diff = diff;
         }
-        return 1u32;
+        1u32
       })
       .sum::<u32>()
-      .to_string(),
+      .into(),
   )
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&Reports::parse(input)?)
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
   #[test]
-  fn test_process() -> anyhow::Result<()> {
-    let input = r#"
-      7 6 4 2 1
-      1 2 7 8 9
-      9 7 6 2 1
-      1 3 2 4 5
-      8 6 4 4 1
-      1 3 6 7 9
-    "#;
-    assert_eq!("2", process(input)?);
-    Ok(())
+  fn test_process_reports_bad_levels() {
+    let err = process("7 6 4 2 1\n1 2 x 8 9\n").unwrap_err();
+    let err = err.downcast_ref::<aoc_tools::ParseError>().unwrap();
+    assert_eq!((2, 5), (err.line, err.column));
   }
 }
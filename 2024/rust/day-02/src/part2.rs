@@ -1,5 +1,8 @@
+use aoc_tools::Answer;
 use itertools::Itertools;
 
+use crate::model::Reports;
+
 pub fn levels_ok(levels: impl IntoIterator<Item = i32>) -> bool {
   let mut last_diff = 0;
   for (a, b) in levels.into_iter().tuple_windows() {
@@ -12,7 +15,7 @@ pub fn levels_ok(levels: impl IntoIterator<Item = i32>) -> bool {
     }
     last_diff = diff;
   }
-  return true;
+  true
 }
 
 pub fn levels_ok_with_dampening(levels: &[i32]) -> bool {
@@ -32,28 +35,29 @@ pub fn levels_ok_with_dampening(levels: &[i32]) -> bool {
       return true;
     }
   }
-  return false;
+  false
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> anyhow::Result<String> {
+pub fn solve(reports: &Reports) -> anyhow::Result<Answer> {
   Ok(
-    input
-      .lines()
-      .map(|l| {
-        l.split_whitespace()
-          .map(|s| s.parse::<i32>().unwrap())
-          .collect::<Vec<i32>>()
-      })
-      .filter(|l| !l.is_empty())
-      .map(|levels| -> u32 { levels_ok_with_dampening(&levels).into() })
+    reports
+      .0
+      .iter()
+      .map(|levels| -> u32 { levels_ok_with_dampening(levels).into() })
       .sum::<u32>()
-      .to_string(),
+      .into(),
   )
 }
 
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&Reports::parse(input)?)
+}
+
 #[cfg(test)]
 mod tests {
+  use aoc_tools::strategy::reports;
+  use proptest::prelude::*;
   use rstest::rstest;
 
   use super::*;
@@ -70,18 +74,14 @@ mod tests {
     assert_eq!(levels_ok_with_dampening(levels), expected);
   }
 
-  #[test]
-  fn test_process() -> anyhow::Result<()> {
-    let input = r#"
-      7 6 4 2 1
-      1 2 7 8 9
-      9 7 6 2 1
-      1 3 2 4 5
-      8 6 4 4 1
-      1 3 6 7 9
-      89 91 94 96 97 99 98 98
-    "#;
-    assert_eq!("4", process(input)?);
-    Ok(())
+  proptest! {
+    /// The Problem Dampener only ever makes more reports safe.
+    #[test]
+    fn test_dampening_keeps_safe_reports(input in reports(1..20, 1..=8)) {
+      for levels in Reports::parse(&input).unwrap().0 {
+        let safe = crate::part1::solve(&Reports(vec![levels.clone()])).unwrap() == 1u32;
+        prop_assert!(!safe || levels_ok_with_dampening(&levels));
+      }
+    }
   }
 }
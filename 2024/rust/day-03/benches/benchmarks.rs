@@ -1,22 +1,60 @@
+use aoc_tools::input::{normalize_input, Inputs};
+use aoc_tools::{Part, Solution};
 use day_03::*;
 
+// Count allocations alongside timings with `--features alloc-profile`.
+#[cfg(feature = "alloc-profile")]
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
 fn main() {
-    // Run registered benchmarks.
-    divan::main();
+  // Run registered benchmarks.
+  divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input1.txt",
-    )))
-    .unwrap();
+fn input(part: Part) -> String {
+  let input = Inputs::for_crate(env!("CARGO_MANIFEST_DIR"))
+    .read(Day03::DAY, Some(part))
+    .unwrap_or_else(|e| panic!("{:#}", e));
+  normalize_input(&input)
 }
 
 #[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input2.txt",
-    )))
-    .unwrap();
+fn parse(bencher: divan::Bencher) {
+  let input = input(Part::One);
+  bencher.bench(|| Day03::parse(divan::black_box(&input)).unwrap());
+}
+
+/// Each part on input parsed up front.
+mod solve {
+  use super::*;
+
+  #[divan::bench]
+  fn part1(bencher: divan::Bencher) {
+    let parsed = Day03::parse(&input(Part::One)).unwrap();
+    bencher.bench(|| part1::solve(divan::black_box(&parsed)).unwrap());
+  }
+
+  #[divan::bench]
+  fn part2(bencher: divan::Bencher) {
+    let parsed = Day03::parse(&input(Part::Two)).unwrap();
+    bencher.bench(|| part2::solve(divan::black_box(&parsed)).unwrap());
+  }
+}
+
+/// Each part from the raw input, parsing included.
+mod end_to_end {
+  use super::*;
+
+  #[divan::bench]
+  fn part1(bencher: divan::Bencher) {
+    let input = input(Part::One);
+    bencher.bench(|| part1::process(divan::black_box(&input)).unwrap());
+  }
+
+  #[divan::bench]
+  fn part2(bencher: divan::Bencher) {
+    let input = input(Part::Two);
+    bencher.bench(|| part2::process(divan::black_box(&input)).unwrap());
+  }
 }
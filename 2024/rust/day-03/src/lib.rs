@@ -1,2 +1,31 @@
+use aoc_tools::Answer;
+use model::Program;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day03;
+
+impl aoc_tools::Solution for Day03 {
+  const DAY: &'static str = "day-03";
+
+  type Parsed = Program;
+
+  fn parse(input: &str) -> anyhow::Result<Program> {
+    Program::parse(input)
+  }
+
+  fn part1(program: &Program) -> anyhow::Result<Answer> {
+    part1::solve(program)
+  }
+
+  fn part2(program: &Program) -> anyhow::Result<Answer> {
+    part2::solve(program)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::Day03);
+}
@@ -0,0 +1,62 @@
+use aoc_tools::parse::{digits, scan_matches};
+use nom::{
+  branch::alt,
+  bytes::complete::tag,
+  combinator::{map, value},
+  sequence::{delimited, separated_pair},
+  IResult,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Instruction {
+  Do,
+  Dont,
+  Multiply(i32, i32),
+}
+
+/// The well-formed instructions found in the corrupted memory, in order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Program(pub Vec<Instruction>);
+
+impl Program {
+  pub fn parse(input: &str) -> anyhow::Result<Program> {
+    Ok(Program(
+      scan_matches(instruction, input)
+        .starting_with(&['d', 'm'])
+        .map(|(_, instruction)| instruction)
+        .collect(),
+    ))
+  }
+}
+
+fn multiply(input: &str) -> IResult<&str, Instruction> {
+  map(
+    delimited(
+      tag("mul("),
+      separated_pair(digits(1, 3), tag(","), digits(1, 3)),
+      tag(")"),
+    ),
+    |(a, b)| Instruction::Multiply(a, b),
+  )(input)
+}
+
+fn instruction(input: &str) -> IResult<&str, Instruction> {
+  alt((
+    value(Instruction::Do, tag("do()")),
+    value(Instruction::Dont, tag("don't()")),
+    multiply,
+  ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() -> anyhow::Result<()> {
+    use Instruction::*;
+    let program = Program::parse("mul(1,2)don't()_mul(3,4]do()émul(1000,1)mul(5,6)")?;
+    assert_eq!(vec![Multiply(1, 2), Dont, Do, Multiply(5, 6)], program.0);
+    Ok(())
+  }
+}
@@ -1,67 +1,29 @@
-use nom::{
-  branch::alt,
-  bytes::complete::tag,
-  character::complete::one_of,
-  combinator::{map_res, recognize, value},
-  multi::many_m_n,
-  sequence::{delimited, separated_pair},
-  IResult,
-};
+use aoc_tools::Answer;
 
-#[derive(Clone, Copy)]
-enum Expr {
-  Do,
-  Dont,
-  Multiply(i32, i32),
-}
-
-fn parse_number(input: &str) -> IResult<&str, i32> {
-  map_res(
-    recognize(many_m_n(1, 3, one_of("1234567890"))),
-    |out: &str| i32::from_str_radix(out, 10),
-  )(input)
-}
-
-fn parse_expr(input: &str) -> IResult<&str, Expr> {
-  map_res(
-    delimited(
-      tag("mul("),
-      separated_pair(parse_number, tag(","), parse_number),
-      tag(")"),
-    ),
-    |(a, b)| -> Result<Expr, ()> { Ok(Expr::Multiply(a, b)) },
-  )(input)
-}
-
-fn parse_do(input: &str) -> IResult<&str, Expr> {
-  value(Expr::Do, tag("do()"))(input)
-}
-
-fn parse_dont(input: &str) -> IResult<&str, Expr> {
-  value(Expr::Dont, tag("don't()"))(input)
-}
+use crate::model::{Instruction, Program};
 
-fn parse(input: &str) -> IResult<&str, Expr> {
-  alt((parse_do, parse_dont, parse_expr))(input)
+pub fn solve(program: &Program) -> anyhow::Result<Answer> {
+  let (_, result) =
+    program.0.iter().fold(
+      (true, 0),
+      |(active, result), instruction| match instruction {
+        Instruction::Do => (true, result),
+        Instruction::Dont => (false, result),
+        Instruction::Multiply(a, b) if active => (active, result + a * b),
+        Instruction::Multiply(..) => (active, result),
+      },
+    );
+  Ok(result.into())
 }
 
 #[tracing::instrument]
-pub fn process(expr: &str) -> anyhow::Result<String> {
-  let mut result = 0;
-  let mut active = true;
-  for i in 0..expr.len() {
-    match parse(expr.split_at(i).1) {
-      Ok((_, Expr::Do)) => active = true,
-      Ok((_, Expr::Dont)) => active = false,
-      Ok((_, Expr::Multiply(a, b))) if active => result += a * b,
-      _ => (),
-    }
-  }
-  Ok(result.to_string())
+pub fn process(expr: &str) -> anyhow::Result<Answer> {
+  solve(&Program::parse(expr)?)
 }
 
 #[cfg(test)]
 mod tests {
+  use proptest::prelude::*;
   use rstest::rstest;
 
   use super::*;
@@ -73,12 +35,27 @@ mod tests {
   #[case("mul(6,9!", 0)]
   #[case("?(12,34)", 0)]
   #[case("mul ( 2 , 4 )", 0)]
-  #[case(
-    "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
-    161
-  )]
+  #[case("don't()émul(2,3)dö()mul(4,5)", 0)]
   fn test_process(#[case] expr: &str, #[case] result: i32) -> anyhow::Result<()> {
-    assert_eq!(result.to_string(), process(expr)?);
+    assert_eq!(result, process(expr)?);
     Ok(())
   }
+
+  /// Corrupted memory without any `don't()`.
+  fn memory() -> impl Strategy<Value = String> {
+    let token = prop_oneof![
+      "mul\\([0-9]{1,3},[0-9]{1,3}\\)",
+      Just("do()".to_owned()),
+      "[a-z(),*!]{0,4}",
+    ];
+    prop::collection::vec(token, 0..30).prop_map(|tokens| tokens.concat())
+  }
+
+  proptest! {
+    /// Without a `don't()` every multiplication stays enabled.
+    #[test]
+    fn test_always_enabled(input in memory()) {
+      prop_assert_eq!(crate::part1::process(&input).unwrap(), process(&input).unwrap());
+    }
+  }
 }
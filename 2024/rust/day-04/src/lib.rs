@@ -1,2 +1,63 @@
+use aoc_tools::Answer;
+use model::WordSearch;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day04;
+
+impl aoc_tools::Solution for Day04 {
+  const DAY: &'static str = "day-04";
+
+  type Parsed = WordSearch;
+
+  fn parse(input: &str) -> anyhow::Result<WordSearch> {
+    WordSearch::parse(input)
+  }
+
+  fn part1(search: &WordSearch) -> anyhow::Result<Answer> {
+    part1::solve(search)
+  }
+
+  fn part2(search: &WordSearch) -> anyhow::Result<Answer> {
+    part2::solve(search)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc_tools::strategy::grid;
+  use aoc_tools::{Day, Part};
+  use proptest::prelude::*;
+
+  use super::*;
+
+  aoc_tools::example_tests!(super::Day04);
+
+  fn transpose(input: &str) -> String {
+    let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    (0..rows[0].len())
+      .map(|x| {
+        rows
+          .iter()
+          .map(|row| row[x] as char)
+          .chain(['\n'])
+          .collect::<String>()
+      })
+      .collect()
+  }
+
+  proptest! {
+    /// Both words read the same across the grid's diagonal.
+    #[test]
+    fn test_transpose(input in grid(b"XMAS", 1..=12, 1..=12)) {
+      let day = Day::of::<Day04>();
+      let run = day.run(&input, &Part::BOTH).unwrap();
+      let transposed = day.run(&transpose(&input), &Part::BOTH).unwrap();
+      for (a, b) in run.parts.iter().zip(&transposed.parts) {
+        prop_assert_eq!(&a.answer, &b.answer);
+      }
+    }
+  }
+}
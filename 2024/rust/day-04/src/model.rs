@@ -0,0 +1,11 @@
+use aoc_tools::grid::{self, Grid};
+
+/// The letters of the word search.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WordSearch(pub Grid<u8>);
+
+impl WordSearch {
+  pub fn parse(input: &str) -> anyhow::Result<WordSearch> {
+    Ok(WordSearch(grid::parse(input, Ok)?))
+  }
+}
@@ -1,70 +1,27 @@
-use itertools::Itertools;
+use aoc_tools::grid::{Grid, NEIGHBORS_8};
+use aoc_tools::Answer;
+use glam::IVec2;
 
-fn adj_search(input: &Vec<Vec<char>>, i: isize, j: isize) -> u32 {
-  let kernel = [
-    [(i, j), (i, j + 1), (i, j + 2), (i, j + 3)],
-    [(i, j), (i + 1, j + 1), (i + 2, j + 2), (i + 3, j + 3)],
-    [(i, j), (i + 1, j), (i + 2, j), (i + 3, j)],
-    [(i, j), (i + 1, j - 1), (i + 2, j - 2), (i + 3, j - 3)],
-    [(i, j), (i, j - 1), (i, j - 2), (i, j - 3)],
-    [(i, j), (i - 1, j - 1), (i - 2, j - 2), (i - 3, j - 3)],
-    [(i, j), (i - 1, j), (i - 2, j), (i - 3, j)],
-    [(i, j), (i - 1, j + 1), (i - 2, j + 2), (i - 3, j + 3)],
-  ];
-  let mut count = 0;
-  let dims = (input.len() as isize, input[0].len() as isize);
-  for indices in kernel {
-    if indices
-      .iter()
-      .any(|(i, j)| *i < 0 || *j < 0 || *i >= input.len() as isize || *j >= input[0].len() as isize)
-    {
-      continue;
-    }
-    if input[indices[0].0 as usize][indices[0].1 as usize] == 'X'
-      && input[indices[1].0 as usize][indices[1].1 as usize] == 'M'
-      && input[indices[2].0 as usize][indices[2].1 as usize] == 'A'
-      && input[indices[3].0 as usize][indices[3].1 as usize] == 'S'
-    {
-      count += 1;
-    }
-  }
-  count
-}
-
-#[tracing::instrument]
-pub fn process(input: &str) -> anyhow::Result<String> {
-  let input = input.lines().map(|s| s.chars().collect_vec()).collect_vec();
+use crate::model::WordSearch;
 
-  let mut count = 0;
-  for (i, line) in input.iter().enumerate() {
-    for (j, c) in line.iter().cloned().enumerate() {
-      if c == 'X' {
-        count += adj_search(&input, i as isize, j as isize);
-      }
-    }
-  }
-
-  Ok(count.to_string())
+fn adj_search(grid: &Grid<u8>, pos: IVec2) -> u32 {
+  NEIGHBORS_8
+    .iter()
+    .filter(|dir| grid.ray(pos, **dir).map(|(_, c)| *c).take(4).eq(*b"XMAS"))
+    .count() as u32
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
+pub fn solve(WordSearch(grid): &WordSearch) -> anyhow::Result<Answer> {
+  let count: u32 = grid
+    .iter()
+    .filter(|(_, c)| **c == b'X')
+    .map(|(pos, _)| adj_search(grid, pos))
+    .sum();
 
-  #[test]
-  fn test_process() -> anyhow::Result<()> {
-    let input = r#"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX"#;
+  Ok(count.into())
+}
 
-    assert_eq!("18", process(input)?);
-    Ok(())
-  }
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&WordSearch::parse(input)?)
 }
@@ -1,66 +1,31 @@
-use itertools::Itertools;
+use aoc_tools::grid::Grid;
+use aoc_tools::Answer;
+use glam::{ivec2, IVec2};
 
-fn index(input: &Vec<Vec<char>>, (i, j): (isize, isize)) -> char {
-  input[i as usize][j as usize]
-}
+use crate::model::WordSearch;
 
-fn check_match(input: &Vec<Vec<char>>, indices: [(isize, isize); 3]) -> bool {
-  index(input, indices[0]) == 'M'
-    && index(input, indices[1]) == 'A'
-    && index(input, indices[2]) == 'S'
+fn check_match(grid: &Grid<u8>, a: IVec2, b: IVec2) -> bool {
+  matches!(
+    (grid.get(a).copied(), grid.get(b).copied()),
+    (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M'))
+  )
 }
 
-fn adj_search(input: &Vec<Vec<char>>, i: isize, j: isize) -> bool {
-  let kernel = [
-    [(i - 1, j - 1), (i, j), (i + 1, j + 1)],
-    [(i + 1, j + 1), (i, j), (i - 1, j - 1)],
-    [(i - 1, j + 1), (i, j), (i + 1, j - 1)],
-    [(i + 1, j - 1), (i, j), (i - 1, j + 1)],
-  ];
-  (check_match(input, kernel[0]) || check_match(input, kernel[1]))
-    && (check_match(input, kernel[2]) || check_match(input, kernel[3]))
+fn adj_search(grid: &Grid<u8>, pos: IVec2) -> bool {
+  check_match(grid, pos + ivec2(-1, -1), pos + ivec2(1, 1))
+    && check_match(grid, pos + ivec2(1, -1), pos + ivec2(-1, 1))
 }
 
-#[tracing::instrument]
-pub fn process(input: &str) -> anyhow::Result<String> {
-  let input = input.lines().map(|s| s.chars().collect_vec()).collect_vec();
-
-  let mut count = 0;
-  for (i, line) in input.iter().enumerate() {
-    if i == 0 || i == input.len() - 1 {
-      continue;
-    }
-    for (j, c) in line.iter().cloned().enumerate() {
-      if j == 0 || j == line.len() - 1 {
-        continue;
-      }
-      if c == 'A' {
-        count += adj_search(&input, i as isize, j as isize) as u32;
-      }
-    }
-  }
+pub fn solve(WordSearch(grid): &WordSearch) -> anyhow::Result<Answer> {
+  let count = grid
+    .iter()
+    .filter(|(pos, c)| **c == b'A' && adj_search(grid, *pos))
+    .count();
 
-  Ok(count.to_string())
+  Ok(count.into())
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  fn test_process() -> anyhow::Result<()> {
-    let input = r#"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX"#;
-
-    assert_eq!("9", process(input)?);
-    Ok(())
-  }
+#[tracing::instrument]
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&WordSearch::parse(input)?)
 }
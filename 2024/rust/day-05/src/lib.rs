@@ -1,2 +1,31 @@
+use aoc_tools::Answer;
+use model::Manual;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day05;
+
+impl aoc_tools::Solution for Day05 {
+  const DAY: &'static str = "day-05";
+
+  type Parsed = Manual;
+
+  fn parse(input: &str) -> anyhow::Result<Manual> {
+    Ok(Manual::parse(input)?)
+  }
+
+  fn part1(manual: &Manual) -> anyhow::Result<Answer> {
+    part1::solve(manual)
+  }
+
+  fn part2(manual: &Manual) -> anyhow::Result<Answer> {
+    part2::solve(manual)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::Day05);
+}
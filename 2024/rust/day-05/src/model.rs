@@ -0,0 +1,197 @@
+use std::cmp::Ordering;
+
+use aoc_tools::parse::{blank_line, digits, lines, parse_all};
+use aoc_tools::ParseError;
+use nom::character::complete::char;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+use nom::IResult;
+
+/// Page numbers have two digits.
+const PAGES: usize = 100;
+
+/// The page ordering rules as a bitmap: bit `b` of row `a` is set when page
+/// `a` must be printed before page `b`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PageOrder {
+  after: [u128; PAGES],
+}
+
+impl PageOrder {
+  pub fn new() -> Self {
+    PageOrder { after: [0; PAGES] }
+  }
+
+  pub fn insert(&mut self, a: u8, b: u8) {
+    self.after[a as usize] |= 1 << b;
+  }
+
+  pub fn precedes(&self, a: u8, b: u8) -> bool {
+    self.after[a as usize] & (1 << b) != 0
+  }
+
+  /// Orders two pages by the rule between them. The puzzle has a rule for
+  /// every pair of pages in an update, so this sorts updates correctly.
+  pub fn cmp(&self, a: u8, b: u8) -> Ordering {
+    if self.precedes(a, b) {
+      Ordering::Less
+    } else if self.precedes(b, a) {
+      Ordering::Greater
+    } else {
+      Ordering::Equal
+    }
+  }
+
+  /// Whether no page in `update` must come before one printed earlier.
+  pub fn is_ordered(&self, update: &[u8]) -> bool {
+    let mut printed = 0u128;
+    for &page in update {
+      if self.after[page as usize] & printed != 0 {
+        return false;
+      }
+      printed |= 1 << page;
+    }
+    true
+  }
+
+  pub fn reorder(&self, update: &[u8]) -> Vec<u8> {
+    let mut update = update.to_vec();
+    update.sort_unstable_by(|a, b| self.cmp(*a, *b));
+    update
+  }
+}
+
+impl Default for PageOrder {
+  fn default() -> Self {
+    PageOrder::new()
+  }
+}
+
+/// The puzzle input, shared by both parts.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Manual {
+  pub rules: PageOrder,
+  pub updates: Vec<Vec<u8>>,
+}
+
+impl Manual {
+  pub fn parse(input: &str) -> Result<Manual, ParseError> {
+    let (rules, updates) = parse_all(
+      input,
+      separated_pair(lines(rule), blank_line, lines(update)),
+    )?;
+    let mut order = PageOrder::new();
+    for (a, b) in rules {
+      order.insert(a, b);
+    }
+    Ok(Manual {
+      rules: order,
+      updates,
+    })
+  }
+}
+
+fn page(input: &str) -> IResult<&str, u8> {
+  digits(2, 2)(input)
+}
+
+fn rule(input: &str) -> IResult<&str, (u8, u8)> {
+  separated_pair(page, char('|'), page)(input)
+}
+
+fn update(input: &str) -> IResult<&str, Vec<u8>> {
+  separated_list1(char(','), page)(input)
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc_tools::error::NomResultExt;
+  use aoc_tools::input::normalize_input;
+  use rstest::rstest;
+
+  use super::*;
+
+  const EXAMPLE: &str = "
+    47|53
+    97|13
+    97|61
+    97|47
+    75|29
+    61|13
+    75|53
+    29|13
+    97|29
+    53|29
+    61|53
+    97|53
+    61|29
+    47|13
+    75|47
+    97|75
+    47|61
+    75|61
+    47|29
+    75|13
+    53|13
+
+    75,47,61,53,29
+    97,61,53,29,13
+    75,29,13
+    75,97,47,61,53
+    61,13,29
+    97,13,75,29,47
+  ";
+
+  #[rstest]
+  #[case("47|53", (47, 53))]
+  #[case("97|61", (97, 61))]
+  #[case("53|13", (53, 13))]
+  fn test_parse_rule(#[case] input: &str, #[case] expected: (u8, u8)) -> anyhow::Result<()> {
+    assert_eq!(expected, rule(input).located(input)?.1);
+    Ok(())
+  }
+
+  #[rstest]
+  #[case("75,47,61,53,29", &[75,47,61,53,29])]
+  #[case("75,29,13", &[75,29,13])]
+  #[case("61,13,29", &[61,13,29])]
+  fn test_parse_update(#[case] input: &str, #[case] expected: &[u8]) -> anyhow::Result<()> {
+    assert_eq!(expected, update(input).located(input)?.1);
+    Ok(())
+  }
+
+  #[test]
+  fn test_parse_manual() -> anyhow::Result<()> {
+    let manual = Manual::parse(&normalize_input(EXAMPLE))?;
+    assert_eq!(6, manual.updates.len());
+    assert!(manual.rules.precedes(47, 53));
+    assert!(!manual.rules.precedes(53, 47));
+
+    let err = Manual::parse("47|153\n\n47,53").unwrap_err();
+    assert_eq!((1, 6), (err.line, err.column));
+    Ok(())
+  }
+
+  #[rstest]
+  #[case(&[75,47,61,53,29], true)]
+  #[case(&[97,61,53,29,13], true)]
+  #[case(&[75,29,13], true)]
+  #[case(&[75,97,47,61,53], false)]
+  #[case(&[61,13,29], false)]
+  #[case(&[97,13,75,29,47], false)]
+  fn test_is_ordered(#[case] update: &[u8], #[case] expected: bool) -> anyhow::Result<()> {
+    let manual = Manual::parse(&normalize_input(EXAMPLE))?;
+    assert_eq!(expected, manual.rules.is_ordered(update));
+    Ok(())
+  }
+
+  #[rstest]
+  #[case(&[75,97,47,61,53], &[97,75,47,61,53])]
+  #[case(&[61,13,29], &[61,29,13])]
+  #[case(&[97,13,75,29,47], &[97,75,47,29,13])]
+  fn test_reorder(#[case] update: &[u8], #[case] expected: &[u8]) -> anyhow::Result<()> {
+    let manual = Manual::parse(&normalize_input(EXAMPLE))?;
+    assert_eq!(expected, manual.rules.reorder(update));
+    Ok(())
+  }
+}
@@ -0,0 +1,4 @@
+fn main() {
+  // Regenerate the example tests when a file is added to examples/.
+  println!("cargo:rerun-if-changed=examples");
+}
@@ -1,2 +1,45 @@
+use aoc_tools::variant::{Variant, Variants};
+use aoc_tools::{Answer, Part};
+use model::Scene;
+
+pub mod model;
 pub mod part1;
 pub mod part2;
+
+pub struct Day06;
+
+impl aoc_tools::Solution for Day06 {
+  const DAY: &'static str = "day-06";
+
+  type Parsed = Scene;
+
+  fn parse(input: &str) -> anyhow::Result<Scene> {
+    Scene::parse(input)
+  }
+
+  fn part1(scene: &Scene) -> anyhow::Result<Answer> {
+    part1::solve(scene)
+  }
+
+  fn part2(scene: &Scene) -> anyhow::Result<Answer> {
+    part2::solve(scene)
+  }
+}
+
+impl Variants for Day06 {
+  fn variants(part: Part) -> Vec<Variant<Scene>> {
+    match part {
+      Part::One => vec![Variant::new("simulate", part1::solve)],
+      Part::Two => vec![
+        Variant::new("reference", part2::reference),
+        Variant::new("fast", part2::solve),
+        Variant::new("fxhash", part2::solve_fxhash),
+      ],
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  aoc_tools::example_tests!(super::Day06);
+}
@@ -0,0 +1,139 @@
+use std::fmt::Display;
+
+use anyhow::anyhow;
+use aoc_tools::dir::{Dir4, DirSet, Direction};
+use aoc_tools::grid::{self, Grid};
+use aoc_tools::sim::{Step, Walker};
+use glam::I16Vec2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cell {
+  Floor,
+  Obstacle,
+}
+
+impl TryFrom<u8> for Cell {
+  type Error = anyhow::Error;
+
+  fn try_from(value: u8) -> anyhow::Result<Self> {
+    match value {
+      b'.' => Ok(Cell::Floor),
+      b'#' => Ok(Cell::Obstacle),
+      _ => Err(anyhow!("unexpected map cell {:?}", value as char)),
+    }
+  }
+}
+
+impl Display for Cell {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Cell::Floor => write!(f, "."),
+      Cell::Obstacle => write!(f, "#"),
+    }
+  }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Guard {
+  pub location: I16Vec2,
+  pub heading: Dir4,
+}
+
+impl Guard {
+  fn ahead(&self) -> I16Vec2 {
+    self.location + I16Vec2::from(self.heading)
+  }
+}
+
+impl Walker for Guard {
+  type Pos = I16Vec2;
+  type Dir = Dir4;
+
+  fn pos(&self) -> I16Vec2 {
+    self.location
+  }
+
+  fn dir(&self) -> Dir4 {
+    self.heading
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct Scene {
+  pub grid: Grid<Cell>,
+  pub guard: Guard,
+}
+
+impl Scene {
+  pub fn parse(input: &str) -> anyhow::Result<Scene> {
+    let (grid, markers) = grid::parse_with_markers(input, b"^", Cell::Floor, Cell::try_from)?;
+    let location = markers.one(b'^')?;
+    Ok(Scene {
+      grid,
+      guard: Guard {
+        location: location.as_i16vec2(),
+        heading: Dir4::North,
+      },
+    })
+  }
+
+  /// The guard's rule: step forward, or turn right when blocked by an obstacle
+  /// (including the extra `obstacle`, if any). Exits on leaving the map.
+  pub fn step(&self, guard: &Guard, obstacle: Option<I16Vec2>) -> Step<Guard, ()> {
+    let ahead = guard.ahead();
+    match self.grid.get(ahead) {
+      None => Step::Exit(()),
+      Some(Cell::Obstacle) => Step::Continue(Guard {
+        heading: guard.heading.turn_right(),
+        ..*guard
+      }),
+      Some(Cell::Floor) if obstacle == Some(ahead) => Step::Continue(Guard {
+        heading: guard.heading.turn_right(),
+        ..*guard
+      }),
+      Some(Cell::Floor) => Step::Continue(Guard {
+        location: ahead,
+        ..*guard
+      }),
+    }
+  }
+
+  /// An empty record of the headings each cell has been crossed with.
+  pub fn visited(&self) -> Grid<DirSet<Dir4>> {
+    Grid::new(self.grid.width(), self.grid.height(), DirSet::new())
+  }
+}
+
+impl Display for Scene {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.grid)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use aoc_tools::input::normalize_input;
+  use glam::i16vec2;
+
+  use super::*;
+
+  #[test]
+  fn test_parse() -> anyhow::Result<()> {
+    let input = "
+      ....#.....
+      .........#
+      ..........
+      ..#.......
+      .......#..
+      ..........
+      .#..^.....
+      ........#.
+      #.........
+      ......#...
+    ";
+    let scene = Scene::parse(&normalize_input(input))?;
+    assert_eq!(i16vec2(4, 6), scene.guard.location);
+    assert_eq!(Some(&Cell::Floor), scene.grid.get(scene.guard.location));
+    Ok(())
+  }
+}
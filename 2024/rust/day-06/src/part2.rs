@@ -1,261 +1,132 @@
-use bitmask::bitmask;
-use core::str;
-use std::{collections::HashMap, fmt::Display};
-
-use glam::{i16vec2, I16Vec2};
-use itertools::Itertools;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
+
+use aoc_tools::sim::{simulate, trace, Outcome};
+use aoc_tools::Answer;
+use fxhash::FxBuildHasher;
+use glam::I16Vec2;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
-bitmask! {
-  mask GridCell: u8 where flags Element {
-    North = 1,
-    East = 1 << 1,
-    South = 1 << 2,
-    West = 1 << 3,
-    Obstacle = 1 << 4,
-  }
-}
+use crate::model::{Guard, Scene};
+
+/// Try an obstacle on each cell of the guard's original route, walking the
+/// guard from its start every time.
+pub fn reference(scene: &Scene) -> anyhow::Result<Answer> {
+  let route = trace(scene.guard, &mut scene.visited(), |guard| {
+    scene.step(guard, None)
+  });
+  let cells = route
+    .path
+    .iter()
+    .map(|guard| guard.location)
+    .filter(|&location| location != scene.guard.location)
+    .collect::<HashSet<_>>();
 
-impl From<u8> for GridCell {
-  fn from(value: u8) -> Self {
-    match value {
-      b'.' => GridCell::none(),
-      b'#' => GridCell::from(Element::Obstacle),
-      b'^' => GridCell::from(Element::North),
-      b'>' => GridCell::from(Element::East),
-      b'v' => GridCell::from(Element::South),
-      b'<' => GridCell::from(Element::West),
-      _ => panic!(),
-    }
-  }
+  Ok(
+    cells
+      .into_iter()
+      .filter(|&obstacle| {
+        let outcome = simulate(scene.guard, &mut scene.visited(), |guard| {
+          scene.step(guard, Some(obstacle))
+        });
+        matches!(outcome, Outcome::Loop(_))
+      })
+      .count()
+      .into(),
+  )
 }
 
-impl Default for GridCell {
-  fn default() -> Self {
-    GridCell::none()
-  }
+pub fn solve(scene: &Scene) -> anyhow::Result<Answer> {
+  Ok(count_loops(scene, preceeders::<RandomState>(scene)).into())
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Hash)]
-struct Guard {
-  location: I16Vec2,
-  direction: I16Vec2,
+/// [`solve`] with the route cells in an `FxHashMap`.
+pub fn solve_fxhash(scene: &Scene) -> anyhow::Result<Answer> {
+  Ok(count_loops(scene, preceeders::<FxBuildHasher>(scene)).into())
 }
 
-impl Guard {
-  pub fn direction_element(&self) -> Element {
-    match (self.direction.x, self.direction.y) {
-      (1, 0) => Element::East,
-      (0, 1) => Element::South,
-      (-1, 0) => Element::West,
-      (0, -1) => Element::North,
-      _ => panic!(),
+/// Only cells on the guard's original route can change it. Map each to the
+/// guard's state just before it first reaches the cell. The guard's own cell
+/// is left out, even if the route comes back to it.
+fn preceeders<H: BuildHasher + Default>(scene: &Scene) -> HashMap<I16Vec2, Guard, H> {
+  let route = trace(scene.guard, &mut scene.visited(), |guard| {
+    scene.step(guard, None)
+  });
+  let mut preceeders = HashMap::with_capacity_and_hasher(10000, H::default());
+  for pair in route.path.windows(2) {
+    if pair[1].location != scene.guard.location {
+      preceeders.entry(pair[1].location).or_insert(pair[0]);
     }
   }
+  preceeders
 }
 
-#[derive(Clone)]
-pub struct Scene {
-  grid: Vec<GridCell>,
-  dims: I16Vec2,
-  guard: Guard,
-}
-
-impl Scene {
-  fn at(&self, loc: I16Vec2) -> GridCell {
-    self.grid[loc.y as usize * self.dims.x as usize + loc.x as usize]
-  }
-  fn at_mut(&mut self, loc: I16Vec2) -> &mut GridCell {
-    &mut self.grid[loc.y as usize * self.dims.x as usize + loc.x as usize]
-  }
-
-  fn contains(&self, loc: I16Vec2) -> bool {
-    (0..self.dims.x).contains(&loc.x) && (0..self.dims.y).contains(&loc.y)
-  }
-}
-
-fn load_map(input: &str) -> Scene {
-  let lines = input.lines().collect_vec();
-  let mut grid = vec![GridCell::none(); lines.len() * lines[0].len()];
-
-  let mut guard = Guard::default();
-  for (i, line) in lines.iter().enumerate() {
-    for (j, c) in line.bytes().enumerate() {
-      grid[i * line.len() + j] = GridCell::from(c);
-      if c == b'^' {
-        guard = Guard {
-          location: i16vec2(j as i16, i as i16),
-          direction: i16vec2(0, -1),
-        };
-      }
-    }
-  }
-  Scene {
-    grid,
-    dims: i16vec2(lines[0].len() as i16, lines.len() as i16),
-    guard,
-  }
+/// Try an obstacle in each cell, starting from just before the guard reaches
+/// it rather than from the start.
+fn count_loops<H: BuildHasher + Send>(
+  scene: &Scene,
+  preceeders: HashMap<I16Vec2, Guard, H>,
+) -> usize {
+  preceeders
+    .into_par_iter()
+    .filter(|(point, from)| {
+      let outcome = simulate(*from, &mut scene.visited(), |guard| {
+        scene.step(guard, Some(*point))
+      });
+      matches!(outcome, Outcome::Loop(_))
+    })
+    .count()
 }
 
-#[derive(Debug)]
-pub enum ExitReason {
-  LeftScene,
-  Loop,
-}
-
-// #[tracing::instrument(skip(scene))]
-pub fn search(scene: &mut Scene, obstacle: I16Vec2) -> ExitReason {
-  scene.at_mut(obstacle).set(Element::Obstacle);
-
-  let result = loop {
-    let next = scene.guard.location + scene.guard.direction;
-    if !scene.contains(next) {
-      break ExitReason::LeftScene;
-    }
-    let next = match scene.at(next) {
-      cell if cell.contains(Element::Obstacle) => Guard {
-        location: scene.guard.location,
-        direction: scene.guard.direction.perp(),
-      },
-      _ => {
-        let guard = Guard {
-          location: next,
-          ..scene.guard
-        };
-        guard
-      }
-    };
-    if scene.at(next.location).contains(next.direction_element()) {
-      break ExitReason::Loop;
-    }
-
-    scene.guard = next;
-    scene.at_mut(next.location).set(next.direction_element());
-  };
-
-  return result;
-}
-
-// #[tracing::instrument(skip(input))]
-pub fn process(input: &str) -> anyhow::Result<String> {
-  let mut scene = load_map(input);
-
-  // let mut preceeders: FxHashMap<I16Vec2, Guard> =
-  //   FxHashMap::with_capacity_and_hasher(10000, FxBuildHasher::default());
-  let mut preceeders = HashMap::with_capacity(10000);
-
-  loop {
-    let next = scene.guard.location + scene.guard.direction;
-    if !scene.contains(next) {
-      break;
-    }
-
-    let next = match scene.at(next) {
-      cell if cell.contains(Element::Obstacle) => Guard {
-        location: scene.guard.location,
-        direction: scene.guard.direction.perp(),
-      },
-      _ => {
-        let guard = Guard {
-          location: next,
-          ..scene.guard
-        };
-        guard
-      }
-    };
-
-    preceeders.entry(next.location).or_insert(scene.guard);
-    scene.guard = next;
-  }
-
-  Ok(
-    preceeders
-      .into_par_iter()
-      .filter_map(move |(point, from)| -> Option<I16Vec2> {
-        let mut scene = Scene {
-          grid: scene.grid.clone(),
-          dims: scene.dims,
-          guard: from,
-        };
-        if let ExitReason::Loop = search(&mut scene, point) {
-          return Some(point);
-        }
-        return None;
-      })
-      .count()
-      .to_string(),
-  )
+pub fn process(input: &str) -> anyhow::Result<Answer> {
+  solve(&Scene::parse(input)?)
 }
 
 #[cfg(test)]
 mod tests {
-  use super::*;
+  use aoc_tools::input::normalize_input;
+  use aoc_tools::strategy::grid;
+  use proptest::prelude::*;
+  use proptest::sample::Index;
 
-  #[test]
-  fn test_load_map() -> anyhow::Result<()> {
-    let input = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-    load_map(&input);
-    Ok(())
-  }
+  use super::*;
 
   #[test]
-  fn test_process() -> anyhow::Result<()> {
-    tracing_subscriber::fmt::init();
-    let input = "....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
-    assert_eq!("6", process(input)?);
+  fn test_no_obstacle_on_guard() -> anyhow::Result<()> {
+    // The guard turns on the spot without leaving its cell.
+    let input = "
+      ....#.
+      ...#^#
+      ....#.
+    ";
+    assert_eq!("0", solve(&Scene::parse(&normalize_input(input))?)?);
     Ok(())
   }
-}
 
-impl Display for GridCell {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    match *self {
-      mask if mask == GridCell::none() => write!(f, "."),
-      mask if mask == GridCell::from(Element::North) => write!(f, "^"),
-      mask if mask == GridCell::from(Element::East) => write!(f, ">"),
-      mask if mask == GridCell::from(Element::South) => write!(f, "v"),
-      mask if mask == GridCell::from(Element::West) => write!(f, "<"),
-      mask if mask == GridCell::from(Element::Obstacle) => write!(f, "#"),
-      mask if mask == Element::North | Element::South => write!(f, "|"),
-      mask if mask == Element::East | Element::West => write!(f, "-"),
-      mask if !mask.contains(Element::Obstacle) => {
-        write!(f, "+")
-      }
-      _ => panic!("No string conversion for {:b}", self.mask),
-    }
+  /// A map with the guard dropped on one of its cells.
+  fn map() -> impl Strategy<Value = String> {
+    (grid(b".....#", 1..=12, 1..=12), any::<Index>()).prop_map(|(map, at)| {
+      let cells = (0..map.len())
+        .filter(|&i| map.as_bytes()[i] != b'\n')
+        .collect::<Vec<_>>();
+      let mut map = map.into_bytes();
+      map[*at.get(&cells)] = b'^';
+      String::from_utf8(map).unwrap()
+    })
   }
-}
 
-impl Display for Scene {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    for line in 0..self.dims.y {
-      for col in 0..self.dims.x {
-        write!(
-          f,
-          "{}",
-          self.grid[line as usize * self.dims.x as usize + col as usize]
-        )?;
-      }
-      writeln!(f, "")?;
+  aoc_tools::variant_tests!(crate::Day06, map());
+
+  proptest! {
+    /// A new obstacle only matters on the guard's route, and can't go where
+    /// the guard starts.
+    #[test]
+    fn test_obstacles_on_route(input in map()) {
+      let scene = Scene::parse(&input).unwrap();
+      let visited = crate::part1::solve(&scene).unwrap().to_string().parse::<usize>()?;
+      let loops = solve(&scene).unwrap().to_string().parse::<usize>()?;
+      prop_assert!(loops < visited, "{} loops from {} visited cells", loops, visited);
     }
-    Ok(())
   }
 }